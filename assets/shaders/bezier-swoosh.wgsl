@@ -1,15 +1,16 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI, easeInQuint};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI, easeInQuint};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, cycle_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
-const cycle_duration = 1.0;
-const cycle_cooldown = 1.0;
-const total_duration = cycle_duration + cycle_cooldown;
+const active_fraction = 0.5;    // Part of the cycle spent drawing, the rest is cooldown
 
 // Z controls relative thickness
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> control_points: array<vec3f, 16>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> curve_count: vec4u;
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> clock: EffectClock;
 
 const midline_color = vec4(0.165, 0.133, 0.988, 1.0);
 const stripe_primary_color = vec4(0.892, 0.624, 1.0, 1.0);
@@ -21,22 +22,23 @@ const sections_per_curve_per_unit: u32 = 40;
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     let time_mode = 0;
 
-    var time: f32 = 0;
-    let real_time = globals.time + 10000;
+    let real_cycle = min(1.0, cycle_progress(clock) / active_fraction);
+    var cycle = real_cycle;
     switch time_mode {
         case 1: {
-            time = 10001.11;         // Tight bend right
-        }
-        default: {
-            time = real_time;
+            cycle = 0.22;         // Tight bend right
         }
+        default: {}
     }
 
-    let real_cycle = min(1.0, fract(real_time / cycle_duration) * total_duration);
-    let cycle = min(1.0, fract(time / cycle_duration) * total_duration);
-
     let coord = (mesh.uv - 0.5) * vec2(2.0, -2.0);
     let curve = calc_curve(
         coord, 
@@ -1,15 +1,16 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals};
 
-#import "shaders/helpers.wgsl"::{PI};
-
-const total_duration = 3.0;
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, cycle_progress, cycle_index};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
 // Z controls relative thickness
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> control_points: array<vec3f, 16>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> curve_count: vec4u;
 @group(#{MATERIAL_BIND_GROUP}) @binding(2) var imageTexture: texture_2d<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(3) var imageSampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(4) var<uniform> clock: EffectClock;
 
 const sections_per_curve_per_unit: u32 = 40;
 
@@ -17,26 +18,29 @@ const sections_per_curve_per_unit: u32 = 40;
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     let time_mode = 0;  // Debugging purposes
-    var time: f32 = 0;
+    var cycle = cycle_progress(clock);
     switch time_mode {
         case 1: {
-            time = 10000.68;         // Tight bend right
+            cycle = 0.56;         // Tight bend right
         }
         case 2: {
-            time = 9999.8;          // Tight bend left
+            cycle = 0.267;        // Tight bend left
         }
         case 3: {
-            time = 10001.983;       // End
+            cycle = 0.994;        // End
         }
         case 4: {
-            time = 10001.7;         // Final straight
-        }
-        default: {
-            time = globals.time + 10000;
+            cycle = 0.9;          // Final straight
         }
+        default: {}
     }
-    let cycle = min(1.0, fract(time / total_duration));
 
     let coord = (mesh.uv - 0.5) * vec2(2.0, -2.0);
     let curve = calc_curve(
@@ -50,7 +54,7 @@ fn fragment(
         return vec4(0.0);
     }
 
-    let texture_mode = i32(floor(time / total_duration) % 6);
+    let texture_mode = i32(cycle_index(clock)) % 6;
     switch texture_mode {
         case 0: {
             return chess(curve.uv);
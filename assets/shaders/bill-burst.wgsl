@@ -1,20 +1,24 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI, rand11, remap, easeInQuint, point_in_quad, project_to_line};
-
-const cycle_duration = 3.0;
-const active_duration = 2.0;
-
-const particle_count = 25;
-const particle_base_width = 0.15;
-const particle_base_height = 0.07;
-const particle_velocity = 5.0;
-const particle_gravity = vec2(0.0, -7.0);
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI, rand11, remap, easeInQuint, point_in_quad, project_to_line};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, active_progress, cycle_index};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
+
+struct BillBurstParams {
+    particle_count: i32,
+    particle_base_width: f32,
+    particle_base_height: f32,
+    particle_velocity: f32,
+    particle_gravity: vec2<f32>,
+    dollar_green: vec4<f32>,
+    dark_green: vec4<f32>,
+}
 
-const dollar_green = vec4(133.0,187.0,101.0, 255.0) / 255.0;
-const dark_green = vec4(50.0,77.0,34.0, 255.0) / 255.0;
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> params: BillBurstParams;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
 
+const active_duration = 2.0;
 
 // TODO: Not entirely satisfied with how the bill edges look (weird projection to plane)
 
@@ -22,11 +26,17 @@ const dark_green = vec4(50.0,77.0,34.0, 255.0) / 255.0;
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     let coord = norm_coord(mesh.uv);
 
     var out = vec4(0.0);
 
-    for (var i = 0; i < particle_count; i++) {
+    for (var i = 0; i < params.particle_count; i++) {
         let seed = rand11(f32(i)+cycle_count());
         let part = particle(coord, seed);
 
@@ -52,16 +62,16 @@ fn bill(uv: vec2f) -> vec4f {
     let edge = 0.07;
     let on_edge = uv.x < edge || 1.0-uv.x < edge || uv.y < edge || 1.0-uv.y < edge;
     if on_edge {
-        return dark_green;
+        return params.dark_green;
     }
 
     let centered = (uv-0.5);
     let in_middle = length(centered) < 0.3;
     if in_middle {
-        return dark_green;
+        return params.dark_green;
     }
 
-    return dollar_green;
+    return params.dollar_green;
 }
 
 struct ParticleHit {
@@ -78,9 +88,9 @@ fn particle(coord: vec2f, seed: f32) -> ParticleHit {
 
     let v0x = rand11(seed);
     let v0y = rand11(v0x);
-    let v0 = vec2(v0x-0.5, v0y) * particle_velocity;
+    let v0 = vec2(v0x-0.5, v0y) * params.particle_velocity;
 
-    let pos = vec3((start_pos + v0 * t + particle_gravity * t * t).xy, 0.0);
+    let pos = vec3((start_pos + v0 * t + params.particle_gravity * t * t).xy, 0.0);
 
     let base_tan = vec3(1.0, 0.0, 0.0);
     let base_bitan = vec3(0.0, 1.0, 0.0);
@@ -104,8 +114,8 @@ fn particle(coord: vec2f, seed: f32) -> ParticleHit {
     var out: ParticleHit;
 
     let ease = easeInQuint(1-t);
-    let particle_height = particle_base_height * ease;
-    let particle_width = particle_base_width * ease;
+    let particle_height = params.particle_base_height * ease;
+    let particle_width = params.particle_base_width * ease;
     let bl = project(pos + (-tangent * particle_width - bitangent * particle_height));
     let tl = project(pos + (-tangent * particle_width + bitangent * particle_height));
     let tr = project(pos + (tangent * particle_width + bitangent * particle_height));
@@ -162,18 +172,14 @@ fn project(input: vec3f) -> vec2f {
     return xy + scaled_norm;
 }
 
-
 fn norm_coord(uv: vec2f) -> vec2f {
     return (uv-0.5)*vec2(2.0, -2.0);
 }
 
 fn cycle_count() -> f32 {
-    let time = globals.time + 10000;
-    return floor(time / cycle_duration);
+    return cycle_index(clock);
 }
 fn cycle() -> f32 {
-    let time = globals.time + 10000;
-    // let time = 1000.0;
-    return min(1.0, fract(time / cycle_duration) * cycle_duration / active_duration);
+    return active_progress(clock, active_duration);
 }
 
@@ -1,8 +1,12 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
+
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> base_color: vec4<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> edge_color: vec4<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> clock: EffectClock;
 
 const speed = 1.0;
 
@@ -10,14 +14,20 @@ const speed = 1.0;
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     // Coordinate relative to middle
     let centered = 2 * (mesh.uv - 0.5);
     let angle = atan2(centered.x, centered.y);
 
     let range = length(centered);
-    let ring = fract(speed * globals.time);
+    let ring = fract(speed * clock.elapsed);
 
-    let sins = 0.25 * sin(angle + 15 * globals.time);
+    let sins = 0.25 * sin(angle + 15 * clock.elapsed);
     var field = 2 * (pow(1 - abs(ring - range), 5.0) - 0.5) - (2 * range) + sins;
     field = clamp(field, -0.1, 1.0);
     let color = (1 - field) * edge_color.xyz + (field) * base_color.xyz;
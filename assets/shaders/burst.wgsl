@@ -1,33 +1,45 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI};
-const cycle_duration = 2.0;
-const anim_duration = 0.5;
-
-const main_thickness = 0.1;
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, active_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
+
+struct BurstParams {
+    main_thickness: f32,
+    secondary_start: vec2<f32>,
+    secondary_end: vec2<f32>,
+    secondary_movement: vec2<f32>,
+    secondary_thickness: f32,
+    edge: f32,
+    _padding: vec2<f32>,
+}
 
-const secondary_start = vec2(0.01, 0.015);
-const secondary_end = vec2(0.6, 0.9);
-const secondary_movement = vec2(0.1, 0.2);
-const secondary_thickness = 0.1;
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> params: BurstParams;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
+// Color ramp, sampled from left to right
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var gradient: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var gradient_sampler: sampler;
 
-const edge = 0.03;
-const inner_color = vec3(0.0, 0.0, 0.0);
-const outer_color = vec3(1.0, 0.9, 0.1);
+const anim_duration = 0.5;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
-    let time = globals.time + 10000;
-    let cycle = min(1.0, cycle_duration * fract(time / cycle_duration) / anim_duration);
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
+    let cycle = active_progress(clock, anim_duration);
 
     let a = pow(cycle + 0.3, 5.0) ;
     let b = 1.1 - pow(1 - cycle, 5.0);
-    let t = (pow(1 - abs(0.2 - cycle), 4.0)) * main_thickness;
+    let t = (pow(1 - abs(0.2 - cycle), 4.0)) * params.main_thickness;
     let main = diamond(vec2(a), vec2(b), t, mesh.uv);
-    let color_main = diamond(vec2(a), vec2(b), t - edge, mesh.uv);
+    let color_main = diamond(vec2(a), vec2(b), t - params.edge, mesh.uv);
 
     var secondary = 0.0;
     var color_secondary = 0.0;
@@ -35,30 +47,30 @@ fn fragment(
         // Secondaries start at max and fade out
         let fi = f32(i) * 2.0;
         let fiv = vec2(fi);
-        let thickness = max(0.0, (1 - 8 * pow(cycle, 2.0))) * secondary_thickness;
+        let thickness = max(0.0, (1 - 8 * pow(cycle, 2.0))) * params.secondary_thickness;
 
-        let movement = cycle * secondary_movement;
+        let movement = cycle * params.secondary_movement;
 
-        let start_top = movement + secondary_start * fi;
-        let start_bot = movement.yx + secondary_start.yx * fi;
+        let start_top = movement + params.secondary_start * fi;
+        let start_bot = movement.yx + params.secondary_start.yx * fi;
 
-        let end_top = cycle * movement + pow(secondary_end, fiv);
-        let end_bot = cycle * movement.yx + pow(secondary_end.yx, fiv);
+        let end_top = cycle * movement + pow(params.secondary_end, fiv);
+        let end_bot = cycle * movement.yx + pow(params.secondary_end.yx, fiv);
 
         secondary += diamond(start_top, end_top, thickness, mesh.uv);
         secondary += diamond(start_bot, end_bot, thickness, mesh.uv);
-        color_secondary += diamond(start_top, end_top, thickness - edge, mesh.uv);
-        color_secondary += diamond(start_bot, end_bot, thickness - edge, mesh.uv);
+        color_secondary += diamond(start_top, end_top, thickness - params.edge, mesh.uv);
+        color_secondary += diamond(start_bot, end_bot, thickness - params.edge, mesh.uv);
     }
 
     let main_field = max(main, secondary);
     let color_field = max(color_main, color_secondary);
 
-    let color = inner_color * color_field + outer_color * (1 - color_field);
+    // Middles at the left of the ramp, edges at the right
+    let color = textureSample(gradient, gradient_sampler, vec2(1.0 - color_field, 0.5)).rgb;
     return vec4(color, main_field);
 }
 
-
 fn diamond(cp1: vec2<f32>, cp2: vec2<f32>, max_width: f32, point: vec2<f32>) -> f32 {
     if length(cp1 - cp2) < max_width {
         // This prevents the whole thing flashing white
@@ -3,21 +3,30 @@
 
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> base_color: vec4<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> edge_color: vec4<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> clock: EffectClock;
 
 const speed = 1.2;
 
-#import "shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     // Coordinate relative to middle
     let centered = 2 * (mesh.uv - 0.5);
     let angle = atan2(centered.x, centered.y);
     let range = length(centered);
 
-    let time = speed * globals.time;
+    let time = speed * clock.elapsed;
 
     let trig = pow(
         ((1 - abs(
@@ -1,26 +1,35 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, active_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
-const cycle_duration = 2.0;
-const speed = 2.0;
-
-const angle_sharpness = 1.5;
+struct CornerSlashParams {
+    angle_sharpness: f32,
+    color: vec4<f32>,
+}
 
-const color = vec3(1.0, 0.0, 1.0);
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> params: CornerSlashParams;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
 
+const active_duration = 1.0;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
-    let time = globals.time + 10000;
-    let cycle = min(1.0, speed * fract(time / cycle_duration));
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
+    let cycle = active_progress(clock, active_duration);
 
     let angle = atan2(mesh.uv.x, mesh.uv.y);                // 0 to -PI/2
     let norm_angle = 1 - 2 * abs((angle * 2.0 / PI) - 0.5); // 0 to 1
-    let angle_falloff = pow(norm_angle, angle_sharpness);
+    let angle_falloff = pow(norm_angle, params.angle_sharpness);
     let time_falloff = 1 - pow(cycle, 5.0);
     let falloff = angle_falloff * time_falloff;
 
@@ -28,7 +37,7 @@ fn fragment(
     let wave = pow(1 - abs(dist - (ease(cycle * 0.4) * 0.15 + 0.8)), 40.0);
     let field = falloff * wave;
 
-    return vec4(color.xyz, field);
+    return vec4(params.color.xyz, field);
 }
 
 fn ease(i: f32) -> f32 {
@@ -0,0 +1,27 @@
+// Debug views that stand in for an effect's color, picked by the shader def
+// `DebugView` specializes the material with. Without one the color is kept.
+//
+// Every effect ends its fragment shader with
+//     return debug_view(effect_color(mesh), mesh.uv);
+
+fn debug_view(color: vec4<f32>, uv: vec2<f32>) -> vec4<f32> {
+#ifdef DEBUG_ALPHA
+    return vec4(vec3(color.a), 1.0);
+#else ifdef DEBUG_UV
+    return vec4(uv, 0.0, 1.0);
+#else ifdef DEBUG_NORM_COORD
+    // The -1 to 1 space most effects draw in, y up. Blue marks the corners
+    // outside the unit circle, where round effects should have faded out
+    let coord = (uv - 0.5) * vec2(2.0, -2.0);
+    return vec4(coord * 0.5 + 0.5, select(0.0, 1.0, length(coord) > 1.0), 1.0);
+#else ifdef DEBUG_NON_FINITE
+    // NaN and infinity are the floats with every exponent bit set
+    let exponents = bitcast<vec4<u32>>(color) & vec4(0x7f800000u);
+    if any(exponents == vec4(0x7f800000u)) {
+        return vec4(1.0, 0.0, 1.0, 1.0);
+    }
+    return color;
+#else
+    return color;
+#endif
+}
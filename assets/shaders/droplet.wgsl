@@ -1,20 +1,27 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI, point_in_tri};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI, point_in_tri};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, cycle_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
 // Note to reader:
 // If you are planning on using this on a particle effect or similar, you probably shouldn't
 // Raindrops are actually mostly round and rain effects are better done in other ways
 // This is just a demonstration / study of making a shape geometrically
 
-const cycle_duration = 3.0;
-const active_duration = cycle_duration;
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> clock: EffectClock;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     let coord = norm_coord(mesh.uv);
     let t = cycle() * 2 * PI;
 
@@ -56,7 +63,6 @@ fn norm_coord(uv: vec2f) -> vec2f {
 }
 
 fn cycle() -> f32 {
-    let time = globals.time + 10000;
-    return min(1.0, fract(time / cycle_duration) * cycle_duration / active_duration);
+    return cycle_progress(clock);
 }
 
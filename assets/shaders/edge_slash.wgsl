@@ -1,23 +1,33 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{easeOutQuint};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{easeOutQuint};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, active_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
-const cycle_duration = 2.0;
-const speed = 2.0;
+struct EdgeSlashParams {
+    edge_sharpness: f32,
+    color: vec4<f32>,
+}
 
-const edge_sharpness = 2.0;
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> params: EdgeSlashParams;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
 
-const color = vec3(0.0, 1.0, 0.5);
+const active_duration = 1.0;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
-    let time = globals.time + 10000;
-    let cycle = min(1.0, speed * fract(time / cycle_duration));
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
+    let cycle = active_progress(clock, active_duration);
 
-    let edge_falloff = pow(1 - abs(mesh.uv.y * 2 - 1.0), edge_sharpness);
+    let edge_falloff = pow(1 - abs(mesh.uv.y * 2 - 1.0), params.edge_sharpness);
     let time_falloff = 1 - pow(cycle, 5.0);
     let falloff = edge_falloff * time_falloff;
 
@@ -25,5 +35,5 @@ fn fragment(
     let wave = pow(1 - abs(dist - (easeOutQuint(cycle * 0.4) * 0.15 + 0.6)), 40.0);
     let field = falloff * wave;
 
-    return vec4(color.xyz, field);
+    return vec4(params.color.xyz, field);
 }
@@ -0,0 +1,39 @@
+// Per-effect timing, set from Rust through `EffectClock`.
+// Each material binds its own clock, e.g.
+// @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> clock: EffectClock;
+
+struct EffectClock {
+    start: f32,     // App time the effect was (re)started at, in seconds
+    elapsed: f32,   // Seconds since start, already scaled by speed
+    speed: f32,
+    cycle: f32,     // Length of one cycle, in seconds
+    looping: u32,   // 0 plays a single cycle and holds its last frame
+    debug_view: u32,    // The debug view the material is specialized for, see debug.wgsl
+    _padding: f32,  // Uniforms need to be a multiple of 16 bytes for WASM
+}
+
+// Seconds into the current cycle
+fn cycle_time(clock: EffectClock) -> f32 {
+    if clock.looping == 0u {
+        return min(clock.elapsed, clock.cycle);
+    }
+    return fract(clock.elapsed / clock.cycle) * clock.cycle;
+}
+
+// Progress through the current cycle, in range [0, 1]
+fn cycle_progress(clock: EffectClock) -> f32 {
+    return cycle_time(clock) / clock.cycle;
+}
+
+// Progress through the first `duration` seconds of the cycle, holding 1 for the rest
+fn active_progress(clock: EffectClock, duration: f32) -> f32 {
+    return min(1.0, cycle_time(clock) / duration);
+}
+
+// How many cycles have completed, for reseeding randomness every cycle
+fn cycle_index(clock: EffectClock) -> f32 {
+    if clock.looping == 0u {
+        return 0.0;
+    }
+    return floor(clock.elapsed / clock.cycle);
+}
@@ -5,7 +5,7 @@
 @group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> mid_color: vec4<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> edge_color: vec4<f32>;
 
-#import "shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
 
 @fragment
 fn fragment(
@@ -1,6 +1,11 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::globals;
 
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> clock: EffectClock;
+
 // This started off with chatGPT. I wanted to see how it would do, and the
 // overall result was a bit mixed. It generated a hash and noise which is to be
 // expected, but I'm not sure which algorithms it used. It just seems like it
@@ -52,7 +57,13 @@ fn fireColor(t: f32) -> vec3f {
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4f {
-    let time = globals.time * 1.0;
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4f {
+    let time = clock.elapsed;
 
     let base = mesh.uv * vec2f(8.0, 3.0) + vec2(0.0, time*0.5);
 
@@ -1,13 +1,23 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> clock: EffectClock;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
-    let time = globals.time;
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
+    let time = clock.elapsed;
     let centered = 2 * (mesh.uv - 0.5);
     let angle = atan2(centered.x, centered.y);
     let dist = length(centered);
@@ -2,8 +2,11 @@
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> sharpness: vec4f;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
 
-#import "shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
 const offset = PI * 2 / 3;
 
@@ -11,9 +14,15 @@ const offset = PI * 2 / 3;
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     var ray = normalize(view.world_position.xyz - mesh.world_position.xyz);
     var norm = dot(mesh.world_normal, ray);
     var alpha = pow(1 - norm, sharpness.x) * 0.3;
-    var color = vec3(0, offset, 2 * offset) + globals.time;
+    var color = vec3(0, offset, 2 * offset) + clock.elapsed;
     return vec4(sin(color), alpha);
 }
@@ -4,19 +4,28 @@
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> base_color: vec4<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> mid_color: vec4<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> edge_color: vec4<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var<uniform> clock: EffectClock;
 
-#import "shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     // Coordinate relative to middle
     let centered = 2 * (mesh.uv - 0.5);
 
-    let angle = atan2(centered.x, centered.y) + 0.1 * globals.time;
+    let angle = atan2(centered.x, centered.y) + 0.1 * clock.elapsed;
     let base_wave = wave(angle, 1.0, 1.0, 7);
-    let secondary_wave = wave(angle + 3 * globals.time, 0.0, 3.0, 5);
+    let secondary_wave = wave(angle + 3 * clock.elapsed, 0.0, 3.0, 5);
     let tertiary_wave = wave(angle, 0.0, 1.0, 1);
     let wave_field = pow(0.9 * base_wave + 0.1 * secondary_wave + 1.0 * tertiary_wave, 2.0);
 
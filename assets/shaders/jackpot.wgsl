@@ -2,7 +2,28 @@
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 #import bevy_pbr::mesh_functions::{get_world_from_local, mesh_position_local_to_clip};
 
-#import "shaders/helpers.wgsl"::{easeInQuint, easeOutElastic, easeOutQuint, easeInCirc, PI, TAU, remap};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{easeInQuint, easeOutElastic, easeOutQuint, easeInCirc, PI, TAU, remap};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, cycle_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
+
+struct JackpotParams {
+    peak: f32, // Percentage, timing
+    color_border: f32, // Percentage, how close to peak change to mid-high colors
+    max_height: f32,
+    width_at_peak: f32, // Relative
+    steepness: f32,
+    line_color: vec4<f32>,
+    line_outline_color: vec4<f32>,
+    color_fade: f32,
+    line_inner_thickness: f32, // Without outline
+    line_outer_thickness: f32, // Including outline
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> params: JackpotParams;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
+// Color ramp, sampled from left to right
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var gradient: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var gradient_sampler: sampler;
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
@@ -16,38 +37,21 @@ struct VertexOutput {
     @location(7) height: f32,
 }
 
-const duration = 2.0;
-const peak = 0.75;  // Percentage, timing
-const color_border = 0.1;   // Percentage, how close to peak change to mid-high colors
-
-const max_height = 0.2;
-const width_at_peak = 0.1;  // Relative
-const steepness = 6.0;
-
-const low_color = vec3(0.2, 0.29, 0.17);        // Dull green
-const mid_color= vec3(0.2235, 1.0, 0.0784);     // Neon green
-const high_color = vec3(1.0);        // 
-const line_color = vec3(1.0);
-const line_outline_color = vec3(0.0);
-const color_fade = 2.0;
-const line_inner_thickness = 0.01;              // Without outline
-const line_outer_thickness = 0.05;              // Including outline
-
 @vertex
 fn vertex(vertex: Vertex) -> VertexOutput {
     var out: VertexOutput;
 
-    let time = (globals.time % duration) / duration;
+    let time = cycle_progress(clock);
 
     // Goes up and down, always in range [0, 1], 1 at peak
     var t = 1.0;
     var width = 1.0;
-    if time < peak {
-        t = pow(time / peak, steepness);
-        width = remap(1-t, 0.0, 1.0, width_at_peak, 1.0);
+    if time < params.peak {
+        t = pow(time / params.peak, params.steepness);
+        width = remap(1-t, 0.0, 1.0, params.width_at_peak, 1.0);
     } else {
-        t = pow((peak+1.0-time), steepness);
-        width = easeInQuint(remap(time, peak, 1.0, 1.0, 0.0))* width_at_peak;
+        t = pow((params.peak+1.0-time), params.steepness);
+        width = easeInQuint(remap(time, params.peak, 1.0, 1.0, 0.0))* params.width_at_peak;
     }
 
     var height = vec3(0.0);
@@ -74,11 +78,17 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     if (abs(mesh.world_normal.y) == 1.0) {
         return vec4(0.0);
     }
 
-    let time = (globals.time % duration) / duration;
+    let time = cycle_progress(clock);
 
     let accel = 10.0 * (pow(time+0.5, 4.0));
     let rev_accel = -40.0 * time;
@@ -89,27 +99,29 @@ fn fragment(
     let baseline = 0.5;
 
     let height = main_wave + secondary_wave + time_wave + baseline;
-    let on_line = abs(mesh.uv.y - height) < line_inner_thickness;
-    let on_outline = abs(mesh.uv.y - height) < line_outer_thickness;
+    let on_line = abs(mesh.uv.y - height) < params.line_inner_thickness;
+    let on_outline = abs(mesh.uv.y - height) < params.line_outer_thickness;
     if (on_line) {
-        return vec4(line_color, 1.0);
+        return vec4(params.line_color.rgb, 1.0);
     } if (on_outline) {
-        return vec4(line_outline_color, 1.0);
+        return vec4(params.line_outline_color.rgb, 1.0);
     } else if (mesh.uv.y < height) {
-        let alpha = color_fade*(height - mesh.uv.y);
+        let alpha = params.color_fade*(height - mesh.uv.y);
 
-        let dist = abs(peak - time);
+        let dist = abs(params.peak - time);
 
-        if dist < color_border {
-            let normdist = (1.0 - dist / color_border);
-            let color = mix(mid_color, high_color, normdist);
-            return vec4(color, alpha);
+        // The right half of the ramp is used close to the peak, the left half elsewhere
+        var ramp = 0.0;
+        if dist < params.color_border {
+            let normdist = (1.0 - dist / params.color_border);
+            ramp = 0.5 + 0.5 * normdist;
         } else {
-            let max_dist = max(peak, 1.0-peak);
-            let normdist = easeInQuint(1.0 - remap(dist, color_border, max_dist, 0.0, 1.0));
-            let color = mix(low_color, mid_color, normdist);
-            return vec4(color, alpha);
+            let max_dist = max(params.peak, 1.0-params.peak);
+            let normdist = easeInQuint(1.0 - remap(dist, params.color_border, max_dist, 0.0, 1.0));
+            ramp = 0.5 * normdist;
         }
+        let color = textureSampleLevel(gradient, gradient_sampler, vec2(ramp, 0.5), 0.0).rgb;
+        return vec4(color, alpha);
     } else {
         return vec4(0.0);
     }
@@ -1,77 +1,86 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI, inverse_lerp};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI, inverse_lerp};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, cycle_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
 // Controls
-const zigzags = 3.0;
-const sharpness = 0.8;
-const max_dip = 0.1;
-const max_bump = 0.075;
-const thickness = 0.1;
-const smoothing = 0.15;
-const color_ratio = 0.8;
-const inner_color = vec4(1.0);
-const outer_color = vec4(0.3, 0.4, 1.0, 1.0);
-const wave_thickness = 3.0;
-const wave_speed = 18.0;
+struct LightningParams {
+    zigzags: f32,
+    sharpness: f32,
+    max_dip: f32,
+    max_bump: f32,
+    thickness: f32,
+    smoothing: f32,
+    wave_thickness: f32,
+    wave_speed: f32,
+}
 
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> params: LightningParams;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
+// Color ramp, sampled from left to right
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var gradient: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var gradient_sampler: sampler;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
-    let time = globals.time + 10000;
-    let cycle = fract(time * 0.5);
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
+    let time = clock.elapsed;
+    let cycle = cycle_progress(clock);
 
     let shake = pow((1 - cycle), 5.0) * 0.1;
 
-    let scale = (zigzags - (1 - sharpness));
+    let scale = (params.zigzags - (1 - params.sharpness));
     let x = mesh.uv.x * scale + shake * sin(250 * time);
     let y = mesh.uv.y * scale + shake * cos(170 * time);
     let segment = i32(x);
 
-    let dip = max_dip * zigzags;
-    let bump = max_bump * zigzags;
+    let dip = params.max_dip * params.zigzags;
+    let bump = params.max_bump * params.zigzags;
 
     var shape = 1.0;
-    if (x - f32(segment)) < sharpness { // Downwards part of the segment
+    if (x - f32(segment)) < params.sharpness { // Downwards part of the segment
         // Segment start (match previous segment end)
         //y = x-bump
-        let bump_term = -bump * (1.0 - (x - f32(segment)) / sharpness);
+        let bump_term = -bump * (1.0 - (x - f32(segment)) / params.sharpness);
 
         // Segment discontinuity
         // y = x+dip;
-        let dip_term = dip * (x - f32(segment)) / sharpness;
+        let dip_term = dip * (x - f32(segment)) / params.sharpness;
         shape = dip_term + bump_term;
     } else {    // Upwards part of the segment
         // Segment discontinuity start
         // y = x+dip;
-        let dip_term = dip * (1.0 - (x - f32(segment) - sharpness) / (1 - sharpness));
+        let dip_term = dip * (1.0 - (x - f32(segment) - params.sharpness) / (1 - params.sharpness));
 
         // Segment end (match next segment start)
         //y = x-bump
-        let bump_term = -bump * (x - f32(segment) - sharpness) / (1 - sharpness);
+        let bump_term = -bump * (x - f32(segment) - params.sharpness) / (1 - params.sharpness);
 
         shape = dip_term + bump_term;
     }
 
-    let norm_term = inverse_lerp(0.0, smoothing, mesh.uv.y * (1 - mesh.uv.y));
+    let norm_term = inverse_lerp(0.0, params.smoothing, mesh.uv.y * (1 - mesh.uv.y));
     let flash = max(0.0, 1 - cycle * 2.0);
-    let wave_target = sqrt(2.0) * cycle * wave_speed;
+    let wave_target = sqrt(2.0) * cycle * params.wave_speed;
     let diag_pos = mesh.uv.x + mesh.uv.y;
-    let wave = wave_thickness * clamp((1 - pow(wave_target - diag_pos, 2.0)), 1 / wave_thickness, 1.0);
-    let threshold = norm_term * thickness * flash * wave;
+    let wave = params.wave_thickness * clamp((1 - pow(wave_target - diag_pos, 2.0)), 1 / params.wave_thickness, 1.0);
+    let threshold = norm_term * params.thickness * flash * wave;
 
     let field = length(x - y + shape);
 
-    var color = vec4(0.0);
-
-    if field < color_ratio * threshold {
-        color = inner_color;
-    } else if field < threshold {
-        color = outer_color;
+    if field >= threshold {
+        return vec4(0.0);
     }
 
-    return color;
+    // The core of the bolt at the left of the ramp, its edge at the right
+    return textureSampleLevel(gradient, gradient_sampler, vec2(field / threshold, 0.5), 0.0);
 }
@@ -11,18 +11,27 @@ struct LFPack {
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> base_color: vec4<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> edge_color: vec4<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> pack: LFPack;
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var<uniform> clock: EffectClock;
 
-#import "shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     let speed = pack.speed;
     let angle = pack.angle;
     let layer_count = pack.layer_count;
         
-    let time = globals.time * speed;
+    let time = clock.elapsed * speed;
     let wave = cos(time);
     let centered = 2 * (mesh.uv - 0.5);
 
@@ -1,26 +1,38 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, cycle_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
-const cycle_duration = 4.0;
-const active_duration = cycle_duration;
+struct MandelbrotParams {
+    max_iters: f32,
+    _padding: vec3<f32>,
+}
 
-const max_iters = 50.0;
-const background_color = vec4(0.01, 0.02, 0.15, 0.1);
-const edge_color = vec4(1.0, 1.0, 0.0, 1.0);
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> params: MandelbrotParams;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
+// Color ramp, sampled from left to right
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var gradient: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var gradient_sampler: sampler;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     let scale = 1.2;
     let offset = vec2(-0.55, 0.0);
     let coord = scale*(norm_coord(mesh.uv) + offset);
     let bound = sqrt(2.0)*scale;
 
     let phase = sin(PI * cycle());
-    let loops = i32(phase * max_iters);
+    let loops = i32(phase * params.max_iters);
     // Mandelbrot iteration fc(z) = z^2 + c, where c is the original coordinate
     var z = vec2(0.0);
     var iters = -1;
@@ -37,14 +49,13 @@ fn fragment(
         }
     }
 
-
     var color1 = vec4(0.0);
     if iters == -1 {
         // In the shape
         return vec4(0.0, 0.0, 0.0, 1.0);
     }
     let t = pow(f32(iters) / f32(loops), 2.0);
-    return mix(background_color, edge_color, t);
+    return textureSampleLevel(gradient, gradient_sampler, vec2(t, 0.5), 0.0);
 }
 
 fn norm_coord(uv: vec2f) -> vec2f {
@@ -52,6 +63,5 @@ fn norm_coord(uv: vec2f) -> vec2f {
 }
 
 fn cycle() -> f32 {
-    let time = globals.time + 10000;
-    return min(1.0, fract(time / cycle_duration) * cycle_duration / active_duration);
+    return cycle_progress(clock);
 }
@@ -3,21 +3,29 @@
 
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> base_color: vec4<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> edge_color: vec4<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> clock: EffectClock;
 
-const duration: f32 = 3.0;
 const ring_thickness: f32 = 0.02;
 const rings: i32 = 3;
 const offset: f32 = 0.08;
 
-#import "shaders/helpers.wgsl"::{PI, easeOutQuint};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI, easeOutQuint};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, cycle_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     let centered = 2 * (mesh.uv - 0.5);
     let normdist = length(centered);
-    let time = (globals.time % duration) / duration;  // Normalized time
+    let time = cycle_progress(clock);
 
     let half_ring = ring_thickness / 2.0;
 
@@ -1,17 +1,23 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI, remap, signed_distance_from_line};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI, remap, signed_distance_from_line};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, cycle_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
-const cycle_duration = 3.0;
-const speed = 1.0;
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> clock: EffectClock;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
-    let time = globals.time + 10000;
-    let cycle = min(1.0, speed * fract(time / cycle_duration));
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
+    let cycle = cycle_progress(clock);
     let coord = (mesh.uv - 0.5)*2;
     let angle = remap(
         atan2(coord.x, coord.y),
@@ -1,9 +1,10 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI, rand11, remap, easeOutCirc};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI, rand11, remap, easeOutCirc};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, active_progress, cycle_index};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
-const cycle_duration = 3.0;
 const active_duration = 2.0;
 
 const particle_count = 20;
@@ -12,11 +13,18 @@ const particle_velocity = 4.0;
 const particle_gravity = 0.5;
 
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> effect: vec4u;   // Ah web
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     let coord = norm_coord(mesh.uv);
 
     var out = vec4(0.0);
@@ -149,13 +157,11 @@ fn norm_coord(uv: vec2f) -> vec2f {
 }
 
 fn cycle_count() -> f32 {
-    let time = globals.time + 10000;
-    return floor(time / cycle_duration);
+    return cycle_index(clock);
 }
 
 fn cycle() -> f32 {
-    let time = globals.time + 10000;
-    return min(1.0, fract(time / cycle_duration) * cycle_duration / active_duration);
+    return active_progress(clock, active_duration);
 }
 
 
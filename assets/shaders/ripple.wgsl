@@ -2,7 +2,19 @@
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 #import bevy_pbr::mesh_functions::{get_world_from_local, mesh_position_local_to_clip};
 
-#import "shaders/helpers.wgsl"::{easeInQuint, easeOutQuint, easeInCirc, PI};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{easeInQuint, easeOutQuint, easeInCirc, PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, cycle_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
+
+struct RippleParams {
+    edge_color: vec4<f32>,
+    base_color: vec4<f32>,
+    max_height: f32,
+    ring_thickness: f32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> params: RippleParams;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
@@ -16,13 +28,6 @@ struct VertexOutput {
     @location(7) height: f32,
 }
 
-const duration = 2.0;
-const edge_color = vec4(1.0, 0.0, 0.0, 1.0);
-const base_color = vec4(0.0, 1.0, 0.0, 1.0);
-
-const max_height = 0.2;
-const ring_thickness = 0.6;
-
 @vertex
 fn vertex(vertex: Vertex) -> VertexOutput {
     var out: VertexOutput;
@@ -38,17 +43,17 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 
     // Coordinate relative to middle
     let centered = 2 * (vertex.uv - 0.5);
-    //let time = easeOutQuint((globals.time % duration) / duration);
-    let time = (globals.time % duration) / duration;
+    //let time = easeOutQuint(cycle_progress(clock));
+    let time = cycle_progress(clock);
     //let time = 1.0;
 
-    let half_thickness = ring_thickness / 2.0;
+    let half_thickness = params.ring_thickness / 2.0;
     let norm_t = easeOutQuint(time) * (1.0 - half_thickness); // From 0 to 
     let target_dist = abs(length(centered) - norm_t);
     let dist_fade = 1.0-pow((PI / 2.0) * target_dist / half_thickness, 2.0);
 
     // TODO: This should but doesn't take the scale into account
-    out.height = dist_fade * max_height - easeInCirc(time);
+    out.height = dist_fade * params.max_height - easeInCirc(time);
 
     let offset = vertex.normal * out.height;
     out.position.x += offset.x;
@@ -62,8 +67,14 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     if mesh.height <= 0.01{
         return vec4(0.0);
     }
-    return mix(edge_color, base_color, mesh.height / max_height);
+    return mix(params.edge_color, params.base_color, mesh.height / params.max_height);
 }
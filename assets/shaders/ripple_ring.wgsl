@@ -3,20 +3,28 @@
 
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> base_color: vec4<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> edge_color: vec4<f32>;
-@group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> pack: vec4f;
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> pack: vec4f;   // ring_thickness, padding for WASM
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var<uniform> clock: EffectClock;
 
 
-#import "shaders/helpers.wgsl"::{PI, easeOutQuint};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI, easeOutQuint};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, cycle_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
-    let duration = pack.x;
-    let ring_thickness = pack.y;
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
+    let ring_thickness = pack.x;
     // Coordinate relative to middle
     let centered = 2 * (mesh.uv - 0.5);
-    let time = easeOutQuint((globals.time % duration) / duration);
+    let time = easeOutQuint(cycle_progress(clock));
 
     let normdist = length(centered);
 
@@ -1,60 +1,63 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI};
-
-const cycle_duration = 3.0;
-const speed = 1.0;
-
-const gravity = 9.0;
-const friction = 0.8;
-const land_speed_loss = 0.5;
-const elasticity = 0.4;
-
-const min_angle = PI / 8;
-const max_angle = PI * 0.35;
-
-const min_velocity = 1.0;
-const max_velocity = 2.5;
-const size_vel_influence = 0.5;
-
-const min_size = 0.007;
-const max_size = 0.025;
-
-const min_start = 0.0;
-const max_start = 0.1;
-
-const border = 0.5;
-const border_color = vec4(0.13);
-const inner_color = vec4(0.33);
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, cycle_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
+
+struct RocksParams {
+    gravity: f32,
+    friction: f32,
+    land_speed_loss: f32,
+    elasticity: f32,
+    min_angle: f32,
+    max_angle: f32,
+    min_velocity: f32,
+    max_velocity: f32,
+    size_vel_influence: f32,
+    min_size: f32,
+    max_size: f32,
+    min_start: f32,
+    max_start: f32,
+    border: f32,
+    border_color: vec4<f32>,
+    inner_color: vec4<f32>,
+    seed: i32,
+    amount: i32,
+}
 
-const seed = 420;
-const amount = 30;
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> params: RocksParams;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
-    let time = globals.time + 1000;
-    let cycle = min(1.0, speed * fract(time / cycle_duration));
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
+    let cycle = cycle_progress(clock);
     let coords = (mesh.uv - vec2(0.0, 1.0)) * vec2(1.0, -1.0);
 
     var field = vec3(0.0);
 
-    for (var i = seed; i < seed + amount; i++) {
-        let angle = rand(i, 1.234) * (max_angle - min_angle) + min_angle;
+    for (var i = params.seed; i < params.seed + params.amount; i++) {
+        let angle = rand(i, 1.234) * (params.max_angle - params.min_angle) + params.min_angle;
         let size_rand = rand(i, 2.345);
-        let size = size_rand * (max_size - min_size) + min_size;
-        let size_influence = max(0.0, 1.0 - size_vel_influence * pow(size_rand, 10.0));
-        let velocity = (rand(i, 3.456) * (max_velocity - min_velocity) + min_velocity) * size_influence;
-        let start_x = rand(i, 4.567) * (max_start - min_start) + min_start;
-        let start_y = rand(i, 5.678) * (max_start - min_start) + min_start;
+        let size = size_rand * (params.max_size - params.min_size) + params.min_size;
+        let size_influence = max(0.0, 1.0 - params.size_vel_influence * pow(size_rand, 10.0));
+        let velocity = (rand(i, 3.456) * (params.max_velocity - params.min_velocity) + params.min_velocity) * size_influence;
+        let start_x = rand(i, 4.567) * (params.max_start - params.min_start) + params.min_start;
+        let start_y = rand(i, 5.678) * (params.max_start - params.min_start) + params.min_start;
 
         let launch = vec2(cos(angle), sin(angle)) * velocity;
 
         // First bounce time
-        let disc = sqrt(pow(launch.y, 2.0) + 2 * start_y * gravity);
-        let t0 = (launch.y + disc) / gravity;
+        let disc = sqrt(pow(launch.y, 2.0) + 2 * start_y * params.gravity);
+        let t0 = (launch.y + disc) / params.gravity;
 
         var rock_y = 0.0;
         var rock_x = 0.0;
@@ -62,24 +65,24 @@ fn fragment(
         rock_x = start_x + launch.x * min(cycle, t0);
         if cycle < t0 {
             // On first bounce
-            rock_y = launch.y * cycle - gravity * pow(cycle, 2.0) / 2.0 + start_y;
+            rock_y = launch.y * cycle - params.gravity * pow(cycle, 2.0) / 2.0 + start_y;
         } else {
             // On subsequent bounces
-            let impact_velocity = gravity * t0 - launch.y;
+            let impact_velocity = params.gravity * t0 - launch.y;
             var total_t = t0;
             for (var bounce = 1; bounce < 4; bounce++) {
-                let bounce_vel = pow(elasticity, f32(bounce)) * impact_velocity;
-                let tb = 2 * bounce_vel / gravity;
+                let bounce_vel = pow(params.elasticity, f32(bounce)) * impact_velocity;
+                let tb = 2 * bounce_vel / params.gravity;
                 let bounce_t = cycle - total_t;
 
                 if bounce_t > tb {
                     // This bounce has been passed
-                    rock_x += launch.x * pow(land_speed_loss, f32(bounce)) * tb;
+                    rock_x += launch.x * pow(params.land_speed_loss, f32(bounce)) * tb;
                     total_t += tb;
                 } else {
                     // Yet to complete this bounce
-                    rock_y = bounce_vel * bounce_t - gravity * pow(bounce_t, 2.0) / 2.0;
-                    rock_x += launch.x * pow(land_speed_loss, f32(bounce)) * bounce_t;
+                    rock_y = bounce_vel * bounce_t - params.gravity * pow(bounce_t, 2.0) / 2.0;
+                    rock_x += launch.x * pow(params.land_speed_loss, f32(bounce)) * bounce_t;
                     break;
                 }
             }
@@ -88,7 +91,7 @@ fn fragment(
         let rock_pos = vec2(rock_x, rock_y + size);
         let dist = length(coords - rock_pos);
         if field.x == 0 {
-            field.y += step(abs(dist - size), size * border);
+            field.y += step(abs(dist - size), size * params.border);
         }
         field.x += step(dist, size);
     }
@@ -99,10 +102,10 @@ fn fragment(
 
     let fade = 1 - pow(cycle, 5.0);
     if field.y > 0.0 {
-        return vec4(border_color.xyz, fade);
+        return vec4(params.border_color.xyz, fade);
     }
 
-    return vec4(inner_color.xyz, fade);
+    return vec4(params.inner_color.xyz, fade);
 }
 
 fn rand(index: i32, mul: f32) -> f32 {
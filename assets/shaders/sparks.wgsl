@@ -1,50 +1,53 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI};
-
-const cycle_duration = 3.0;
-const speed = 1.0;
-
-const gravity = 8.0;
-const land_speed_loss = 0.5;
-const elasticity = 0.4;
-
-const min_angle = PI / 6;
-const max_angle = PI * 0.3;
-
-const min_velocity = 1.8;
-const max_velocity = 5.0;
-const size_vel_influence = 0.5;
-const deceleration_x = 0.2;
-
-const min_size = 0.02;
-const max_size = 0.04;
-const shrink_speed = 0.08;
-
-const min_start = 0.0;
-const max_start = 0.2;
-
-const border = 0.2;
-const border_color = vec4(1.0, 0.7, 0.3, 1.0);
-const inner_color = vec4(1.0, 0.8, 0.6, 1.0);
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, cycle_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
+
+struct SparksParams {
+    gravity: f32,
+    land_speed_loss: f32,
+    elasticity: f32,
+    min_angle: f32,
+    max_angle: f32,
+    min_velocity: f32,
+    max_velocity: f32,
+    size_vel_influence: f32,
+    deceleration_x: f32,
+    min_size: f32,
+    max_size: f32,
+    shrink_speed: f32,
+    min_start: f32,
+    max_start: f32,
+    border: f32,
+    border_color: vec4<f32>,
+    inner_color: vec4<f32>,
+    seed: i32,
+    amount: i32,
+}
 
-const seed = 420;
-const amount = 40;
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> params: SparksParams;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
-    let time = globals.time + 1000;
-    let cycle = min(1.0, speed * fract(time / cycle_duration));
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
+    let cycle = cycle_progress(clock);
     let coords = (mesh.uv - vec2(0.0, 1.0)) * vec2(1.0, -1.0);
 
     var field = 0.0;
 
-    for (var i = seed; i < seed + amount; i++) {
+    for (var i = params.seed; i < params.seed + params.amount; i++) {
         let size_rand = rand(i, 2.345);
-        let size = max(0.0, size_rand * (max_size - min_size) + min_size - shrink_speed * cycle);
+        let size = max(0.0, size_rand * (params.max_size - params.min_size) + params.min_size - params.shrink_speed * cycle);
         let size_offset = vec2(0.0, size);
         let front_rock = ball(i, cycle + 0.01, size_rand) + size_offset;
         let back_rock = ball(i, cycle, size_rand) + size_offset;
@@ -61,21 +64,21 @@ fn fragment(
         return vec4(0.0);
     }
 
-    let color = inner_color.xyz * field + border_color.xyz * (1 - field);
+    let color = params.inner_color.xyz * field + params.border_color.xyz * (1 - field);
     return vec4(color, 1.0);
 }
 
 fn ball(i: i32, t: f32, size_rand: f32) -> vec2f {
-    let angle = rand(i, 1.234) * (max_angle - min_angle) + min_angle;
-    let size_influence = max(0.0, 1.0 - size_vel_influence * pow(size_rand, 10.0));
-    let velocity = (rand(i, 3.456) * (max_velocity - min_velocity) + min_velocity) * size_influence;
-    let start_x = rand(i, 4.567) * (max_start - min_start) + min_start;
-    let start_y = rand(i, 5.678) * (max_start - min_start) + min_start;
+    let angle = rand(i, 1.234) * (params.max_angle - params.min_angle) + params.min_angle;
+    let size_influence = max(0.0, 1.0 - params.size_vel_influence * pow(size_rand, 10.0));
+    let velocity = (rand(i, 3.456) * (params.max_velocity - params.min_velocity) + params.min_velocity) * size_influence;
+    let start_x = rand(i, 4.567) * (params.max_start - params.min_start) + params.min_start;
+    let start_y = rand(i, 5.678) * (params.max_start - params.min_start) + params.min_start;
 
     let launch = vec2(cos(angle), sin(angle)) * velocity;
 
     let rock_x = start_x + launch.x * t;
-    let rock_y = launch.y * t - gravity * pow(t, 2.0) / 2.0 + start_y;
+    let rock_y = launch.y * t - params.gravity * pow(t, 2.0) / 2.0 + start_y;
 
     let rock_pos = vec2(rock_x, rock_y);
     return rock_pos;
@@ -1,49 +1,59 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI, perlinNoise2, easeOutQuint, rand11};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI, perlinNoise2, easeOutQuint, rand11};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, active_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
+
+struct SugarCoatParams {
+    particle_amount: i32,
+    particle_seed: i32,
+    particle_gravity: f32,
+    particle_min_angle: f32,
+    particle_max_angle: f32,
+    particle_min_velocity: f32,
+    particle_max_velocity: f32,
+    particle_size_vel_influence: f32,
+    particle_min_size: f32,
+    particle_max_size: f32,
+    particle_shrink_speed: f32,
+    particle_start_center: vec2<f32>,
+    particle_start_distance: f32,
+    particle_inner_color: vec4<f32>,
+    particle_border_color: vec4<f32>,
+}
 
-const cycle_duration = 3.0;
-const active_duration = 2.0;
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> params: SugarCoatParams;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> clock: EffectClock;
 
+const active_duration = 2.0;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     let coord = norm_coord(mesh.uv);
 
     // return particles(coord);
     return particles(coord) + powder(coord);
 }
 
-
-const particle_amount = 50;
-const particle_seed = 69;
-const particle_gravity = 2.5;
-const particle_min_angle = -PI / 6;
-const particle_max_angle = PI * 0.2;
-const particle_min_velocity = 0.5;
-const particle_max_velocity = 1.7;
-const particle_size_vel_influence = 0.5;
-const particle_min_size = 0.015;
-const particle_max_size = 0.02;
-const particle_shrink_speed = 0.005;
-const particle_start_center = vec2(-0.3, 0.2);
-const particle_start_distance = 0.5;
-const particle_inner_color = vec3(0.9, 0.85, 0.85);
-const particle_border_color = vec3(0.7, 0.65, 0.65);
-
 fn particles(coord: vec2f) -> vec4f {
     let t = cycle();
     let time_fade = (0.5-0.8*t);
 
     var out = 0.0;
 
-    for (var i = 0; i <  particle_amount; i++) {
-        let key = f32(i+particle_seed);
+    for (var i = 0; i <  params.particle_amount; i++) {
+        let key = f32(i+params.particle_seed);
         let size_rand = rand11(key);
-        let size = max(0.0, size_rand * (particle_max_size - particle_min_size) + particle_min_size - particle_shrink_speed * t);
+        let size = max(0.0, size_rand * (params.particle_max_size - params.particle_min_size) + params.particle_min_size - params.particle_shrink_speed * t);
         let size_offset = vec2(0.0, size);
         let front_rock = point_in_arc(key, t + 0.01, size_rand) + size_offset;
         let back_rock = point_in_arc(key, t, size_rand) + size_offset;
@@ -60,23 +70,23 @@ fn particles(coord: vec2f) -> vec4f {
         return vec4(0.0);
     }
 
-    let color = mix(particle_inner_color, particle_border_color, out);
+    let color = mix(params.particle_inner_color.rgb, params.particle_border_color.rgb, out);
     return vec4(color.xyz, time_fade);
 }
 
 fn point_in_arc(key: f32, t: f32, size_rand: f32) -> vec2f {
-    let angle = rand11(key+1.0) * (particle_max_angle - particle_min_angle) + particle_min_angle;
-    let size_influence = max(0.0, 1.0 - particle_size_vel_influence * pow(size_rand, 10.0));
-    let velocity = (rand11(key+2.0) * (particle_max_velocity - particle_min_velocity) + particle_min_velocity) * size_influence;
+    let angle = rand11(key+1.0) * (params.particle_max_angle - params.particle_min_angle) + params.particle_min_angle;
+    let size_influence = max(0.0, 1.0 - params.particle_size_vel_influence * pow(size_rand, 10.0));
+    let velocity = (rand11(key+2.0) * (params.particle_max_velocity - params.particle_min_velocity) + params.particle_min_velocity) * size_influence;
     let pos_angle = rand11(key+5.0) * PI * 2;
-    let pos_dist = pow(rand11(key+6.0), 0.5) * particle_start_distance;
+    let pos_dist = pow(rand11(key+6.0), 0.5) * params.particle_start_distance;
     let offset = vec2(cos(pos_angle), sin(pos_angle)) * pos_dist;
-    let spawn_pos = particle_start_center + offset;
+    let spawn_pos = params.particle_start_center + offset;
 
     let launch = vec2(cos(angle), sin(angle)) * velocity;
 
     let rock_x = spawn_pos.x + launch.x * t;
-    let rock_y = launch.y * t - particle_gravity * pow(t, 2.0) / 2.0 + spawn_pos.y;
+    let rock_y = launch.y * t - params.particle_gravity * pow(t, 2.0) / 2.0 + spawn_pos.y;
 
     let rock_pos = vec2(rock_x, rock_y);
     return rock_pos;
@@ -144,6 +154,5 @@ fn norm_coord(uv: vec2f) -> vec2f {
 }
 
 fn cycle() -> f32 {
-    let time = globals.time + 10000;
-    return min(1.0, fract(time / cycle_duration) * cycle_duration / active_duration);
+    return active_progress(clock, active_duration);
 }
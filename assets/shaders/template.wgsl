@@ -1,15 +1,24 @@
 #import bevy_pbr::forward_io::VertexOutput;
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 
-#import "shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock, active_progress};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> clock: EffectClock;
 
-const cycle_duration = 5.0;
 const active_duration = 1.0;
 
 @fragment
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(mesh), mesh.uv);
+}
+
+fn effect_color(
+    mesh: VertexOutput,
+) -> vec4<f32> {
     let coord = norm_coord(mesh.uv);
     return vec4((cycle() * coord).xy, 0.0, 1.0);
 }
@@ -19,6 +28,5 @@ fn norm_coord(uv: vec2f) -> vec2f {
 }
 
 fn cycle() -> f32 {
-    let time = globals.time + 10000;
-    return min(1.0, fract(time / cycle_duration) * cycle_duration / active_duration);
+    return active_progress(clock, active_duration);
 }
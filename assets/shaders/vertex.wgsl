@@ -2,14 +2,15 @@
 #import bevy_pbr::mesh_view_bindings::{globals, view};
 #import bevy_pbr::mesh_functions::{get_world_from_local, mesh_position_local_to_clip};
 
-#import "shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/helpers.wgsl"::{PI};
+#import "embedded://bevy_shader_testing/shaders/effect_time.wgsl"::{EffectClock};
+#import "embedded://bevy_shader_testing/shaders/debug.wgsl"::{debug_view};
 
-const cycle_duration = 3.0;
-const speed = 1.0;
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> clock: EffectClock;
 
 @vertex
 fn vertex(vertex: Vertex) -> VertexOutput {
-    let mirror = i32(globals.time) % 2;
+    let mirror = i32(clock.elapsed) % 2;
     var out: VertexOutput;
 
     out.position = mesh_position_local_to_clip(
@@ -19,7 +20,7 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 
     out.world_normal = vertex.normal;
 
-    out.position.x *= abs(sin(globals.time));
+    out.position.x *= abs(sin(clock.elapsed));
 
     out.uv = vertex.uv;
     out.instance_index = vertex.instance_index;
@@ -28,6 +29,10 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 
 @fragment
 fn fragment(input: VertexOutput) -> @location(0) vec4<f32> {
+    return debug_view(effect_color(input), input.uv);
+}
+
+fn effect_color(input: VertexOutput) -> vec4<f32> {
     return vec4(input.uv, 0.0, 1.0);
 }
 
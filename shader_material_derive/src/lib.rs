@@ -0,0 +1,391 @@
+//! Derive for the `Material` impls shared by the effects in the gallery
+//!
+//! ```ignore
+//! #[derive(Asset, TypePath, AsBindGroup, Clone, ShaderMaterial)]
+//! #[shader("shaders/jackpot.wgsl", vertex, cull = none, alpha = blend)]
+//! pub struct Jackpot {}
+//! ```
+//!
+//! The path is used for the fragment shader, and for the vertex shader too if
+//! `vertex` is given. `cull` is one of `none`, `front` or `back`, `alpha` one of
+//! `opaque`, `blend`, `premultiplied`, `add`, `multiply`, `alpha_to_coverage`
+//! or `mask(cutoff)`. Anything left out uses the `Material` defaults.
+//!
+//! Marking a field `#[clock]` also implements `EffectMaterial` for it, so the
+//! field should be an `EffectClock` uniform.
+//!
+//! With `#[bind_group_data(DebugView)]` the clock's debug view becomes part of
+//! the pipeline key, and the material is specialized with that view's shader
+//! def.
+//!
+//! `MaterialUniforms` is implemented from the `#[uniform(N)]` fields, which
+//! lets tests compare them to the bindings declared in the shader.
+//!
+//! ```ignore
+//! #[derive(Reflect, ParamRanges)]
+//! #[reflect(ParamRanges)]
+//! pub struct LFPack {
+//!     #[range(0.0, 0.2)]
+//!     pub line_thickness: f32,
+//! }
+//! ```
+//!
+//! `ParamRanges` is the other derive, it declares how far the randomizer may
+//! move each field. Fields without a `#[range(min, max)]` are left alone.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_macro_input, Data, DeriveInput, Expr, Ident, LitFloat, LitInt, LitStr, Member, Token,
+};
+
+#[proc_macro_derive(ShaderMaterial, attributes(shader, clock))]
+pub fn derive_shader_material(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(ParamRanges, attributes(range))]
+pub fn derive_param_ranges(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_ranges(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let attr = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("shader"))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &input.ident,
+                "ShaderMaterial needs a #[shader(\"path\", ...)] attribute",
+            )
+        })?;
+    let args: ShaderArgs = attr.parse_args()?;
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let path = &args.path;
+
+    let vertex_shader = args.vertex.then(|| {
+        quote! {
+            fn vertex_shader() -> ::bevy::shader::ShaderRef {
+                #path.into()
+            }
+        }
+    });
+
+    let alpha_mode = args.alpha.map(|alpha| {
+        quote! {
+            fn alpha_mode(&self) -> ::bevy::prelude::AlphaMode {
+                #alpha
+            }
+        }
+    });
+
+    // The view comes from the clock, `specialize` adds its shader def
+    let debug_view = input.attrs.iter().any(|attr| {
+        attr.path().is_ident("bind_group_data")
+            && attr.parse_args::<syn::Path>().is_ok_and(|path| {
+                path.segments
+                    .last()
+                    .is_some_and(|last| last.ident == "DebugView")
+            })
+    });
+
+    let effect_material = marked_field(input, "clock")?.map(|field| {
+        let clock = quote!(::bevy_shader_testing::clock::EffectClock);
+        quote! {
+            impl #impl_generics ::bevy_shader_testing::clock::EffectMaterial
+                for #ident #ty_generics #where_clause
+            {
+                fn clock(&self) -> &#clock {
+                    &self.#field
+                }
+
+                fn clock_mut(&mut self) -> &mut #clock {
+                    &mut self.#field
+                }
+            }
+        }
+    });
+
+    let debug_specialize = debug_view.then(|| {
+        quote! {
+            key.bind_group_data.specialize(descriptor);
+        }
+    });
+    let key = match debug_view {
+        true => quote!(key),
+        false => quote!(_key),
+    };
+
+    // Only needed when there's something to specialize
+    let specialize = (args.cull.is_some() || debug_specialize.is_some()).then(|| {
+        let cull_mode = args.cull.as_ref().map(|cull| {
+            quote! {
+                descriptor.primitive.cull_mode = #cull;
+            }
+        });
+        quote! {
+            fn specialize(
+                _pipeline: &::bevy::pbr::MaterialPipeline,
+                descriptor: &mut ::bevy::render::render_resource::RenderPipelineDescriptor,
+                _layout: &::bevy::mesh::MeshVertexBufferLayoutRef,
+                #key: ::bevy::pbr::MaterialPipelineKey<Self>,
+            ) -> ::core::result::Result<
+                (),
+                ::bevy::render::render_resource::SpecializedMeshPipelineError,
+            > {
+                #cull_mode
+                #debug_specialize
+                ::core::result::Result::Ok(())
+            }
+        }
+    });
+
+    let uniforms = uniform_fields(input)?.into_iter().map(|(binding, field)| {
+        let name = match &field {
+            Member::Named(ident) => ident.to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        };
+        quote! {
+            ::bevy_shader_testing::uniforms::UniformBinding {
+                binding: #binding,
+                field: #name,
+                bytes: ::bevy_shader_testing::uniforms::uniform_bytes(&self.#field),
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::bevy::pbr::Material for #ident #ty_generics #where_clause {
+            #vertex_shader
+
+            fn fragment_shader() -> ::bevy::shader::ShaderRef {
+                #path.into()
+            }
+
+            #alpha_mode
+
+            #specialize
+        }
+
+
+        #effect_material
+
+        impl #impl_generics ::bevy_shader_testing::uniforms::MaterialUniforms
+            for #ident #ty_generics #where_clause
+        {
+            fn uniforms(&self) -> ::std::vec::Vec<::bevy_shader_testing::uniforms::UniformBinding> {
+                ::std::vec![#(#uniforms),*]
+            }
+        }
+    })
+}
+
+fn expand_ranges(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "ParamRanges can only be derived for structs",
+        ));
+    };
+
+    let mut arms = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let Some(attr) = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("range"))
+        else {
+            continue;
+        };
+        let RangeArgs { min, max } = attr.parse_args()?;
+        let name = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
+        };
+        arms.push(quote! {
+            #name => ::core::option::Option::Some(#min..=#max),
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::bevy_shader_testing::ranges::ParamRanges
+            for #ident #ty_generics #where_clause
+        {
+            fn range(&self, field: &str) -> ::core::option::Option<::core::ops::RangeInclusive<f32>> {
+                match field {
+                    #(#arms)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    })
+}
+
+/// The `#[uniform(N)]` fields, with their binding
+fn uniform_fields(input: &DeriveInput) -> syn::Result<Vec<(LitInt, Member)>> {
+    let Data::Struct(data) = &input.data else {
+        return Ok(Vec::new());
+    };
+    let mut uniforms = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let Some(attr) = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("uniform"))
+        else {
+            continue;
+        };
+        // Converted uniforms, like `#[uniform(0, Type)]`, are uploaded as another type
+        let binding = attr.parse_args::<LitInt>().map_err(|err| {
+            syn::Error::new(
+                err.span(),
+                "ShaderMaterial only supports plain #[uniform(N)] fields",
+            )
+        })?;
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        uniforms.push((binding, member));
+    }
+    Ok(uniforms)
+}
+
+/// The field marked with `#[name]`, if any, of which there can only be one
+fn marked_field(input: &DeriveInput, name: &str) -> syn::Result<Option<Member>> {
+    let Data::Struct(data) = &input.data else {
+        return Ok(None);
+    };
+    let mut fields = data
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| field.attrs.iter().any(|attr| attr.path().is_ident(name)))
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        });
+    let field = fields.next();
+    if let Some(extra) = fields.next() {
+        return Err(syn::Error::new_spanned(
+            extra,
+            format!("only one field can be the #[{name}]"),
+        ));
+    }
+    Ok(field)
+}
+
+struct RangeArgs {
+    min: Expr,
+    max: Expr,
+}
+
+impl Parse for RangeArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let min = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let max = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(RangeArgs { min, max })
+    }
+}
+
+struct ShaderArgs {
+    path: LitStr,
+    vertex: bool,
+    cull: Option<TokenStream2>,
+    alpha: Option<TokenStream2>,
+}
+
+impl Parse for ShaderArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = ShaderArgs {
+            path: input.parse()?,
+            vertex: false,
+            cull: None,
+            alpha: None,
+        };
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let key: Ident = input.parse()?;
+            match key.to_string().as_str() {
+                "vertex" => args.vertex = true,
+                "cull" => {
+                    input.parse::<Token![=]>()?;
+                    args.cull = Some(parse_cull(input)?);
+                }
+                "alpha" => {
+                    input.parse::<Token![=]>()?;
+                    args.alpha = Some(parse_alpha(input)?);
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        key,
+                        "expected `vertex`, `cull = ...` or `alpha = ...`",
+                    ))
+                }
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+fn parse_cull(input: ParseStream) -> syn::Result<TokenStream2> {
+    let value: Ident = input.parse()?;
+    let face = quote!(::bevy::render::render_resource::Face);
+    match value.to_string().as_str() {
+        "none" => Ok(quote!(::core::option::Option::None)),
+        "front" => Ok(quote!(::core::option::Option::Some(#face::Front))),
+        "back" => Ok(quote!(::core::option::Option::Some(#face::Back))),
+        _ => Err(syn::Error::new_spanned(
+            value,
+            "expected `none`, `front` or `back`",
+        )),
+    }
+}
+
+fn parse_alpha(input: ParseStream) -> syn::Result<TokenStream2> {
+    let value: Ident = input.parse()?;
+    let mode = quote!(::bevy::prelude::AlphaMode);
+    match value.to_string().as_str() {
+        "opaque" => Ok(quote!(#mode::Opaque)),
+        "blend" => Ok(quote!(#mode::Blend)),
+        "premultiplied" => Ok(quote!(#mode::Premultiplied)),
+        "add" => Ok(quote!(#mode::Add)),
+        "multiply" => Ok(quote!(#mode::Multiply)),
+        "alpha_to_coverage" => Ok(quote!(#mode::AlphaToCoverage)),
+        "mask" => {
+            let content;
+            parenthesized!(content in input);
+            let cutoff: LitFloat = content.parse()?;
+            Ok(quote!(#mode::Mask(#cutoff)))
+        }
+        _ => Err(syn::Error::new_spanned(
+            value,
+            "expected `opaque`, `blend`, `premultiplied`, `add`, `multiply`, \
+             `alpha_to_coverage` or `mask(cutoff)`",
+        )),
+    }
+}
@@ -0,0 +1,307 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    image::{ImageAddressMode, ImageSampler, ImageSamplerDescriptor},
+    input::ButtonState,
+    math::Affine2,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use crate::{camera::FocusCamera, search::Hotkeys};
+
+/// Far enough behind the tiles that spinning cubes don't reach it
+const BACKGROUND_Z: f32 = -1.0;
+/// Edge of a checkerboard square, in world units
+const CHECKER_SQUARE: f32 = 0.1;
+
+/// Puts a backdrop behind the tiles, to judge how the blended effects read on
+/// something other than the clear color
+///
+/// B steps through solid colors, a checkerboard, a gradient and any PNGs dropped
+/// onto the window, such as screenshots of a stage, then back to the clear
+/// color. A dropped image is shown right away.
+pub struct BackgroundsPlugin;
+
+impl Plugin for BackgroundsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_backdrop)
+            .add_systems(Update, (background_keys, fit_backdrop).chain());
+
+        // Dropped files are only read from disk
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(Update, drop_background.before(fit_backdrop));
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Background {
+    name: String,
+    color: Color,
+    image: Option<Handle<Image>>,
+    fit: Fit,
+}
+
+/// How an image covers the backdrop
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Fit {
+    Stretch,
+    /// Keeps its aspect ratio, cropping the sides or the top and bottom
+    Cover,
+    /// Repeats a 2 by 2 texel pattern, each texel this many world units across
+    Repeat(f32),
+}
+
+/// The backgrounds to choose from, `None` showing the clear color
+#[derive(Debug, Resource)]
+struct Backgrounds {
+    list: Vec<Background>,
+    current: Option<usize>,
+}
+
+#[derive(Debug, Component)]
+struct Backdrop;
+
+fn solid(name: &str, color: Color) -> Background {
+    Background {
+        name: name.to_string(),
+        color,
+        image: None,
+        fit: Fit::Stretch,
+    }
+}
+
+fn spawn_backdrop(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let light = [255, 255, 255, 255];
+    let dark = [204, 204, 204, 255];
+    let mut checker = Image::new(
+        Extent3d {
+            width: 2,
+            height: 2,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        [light, dark, dark, light].concat(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    checker.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        ..ImageSamplerDescriptor::nearest()
+    });
+
+    // Dusk sky, from the top down
+    let top = Srgba::rgb(0.1, 0.1, 0.3);
+    let bottom = Srgba::rgb(0.9, 0.5, 0.3);
+    let height = 256;
+    let data = (0..height)
+        .flat_map(|y| {
+            let t = y as f32 / (height - 1) as f32;
+            top.mix(&bottom, t).to_u8_array()
+        })
+        .collect();
+    let mut gradient = Image::new(
+        Extent3d {
+            width: 1,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    gradient.sampler = ImageSampler::linear();
+
+    commands.insert_resource(Backgrounds {
+        list: vec![
+            solid("Black", Color::BLACK),
+            solid("Grey", Color::srgb(0.5, 0.5, 0.5)),
+            solid("White", Color::WHITE),
+            Background {
+                name: "Checkerboard".to_string(),
+                color: Color::WHITE,
+                image: Some(images.add(checker)),
+                fit: Fit::Repeat(CHECKER_SQUARE),
+            },
+            Background {
+                name: "Gradient".to_string(),
+                color: Color::WHITE,
+                image: Some(images.add(gradient)),
+                fit: Fit::Stretch,
+            },
+        ],
+        current: None,
+    });
+
+    commands.spawn((
+        Backdrop,
+        Mesh3d(meshes.add(Rectangle::new(1.0, 1.0))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            unlit: true,
+            ..default()
+        })),
+        Transform::from_xyz(0.0, 0.0, BACKGROUND_Z),
+        Visibility::Hidden,
+    ));
+}
+
+fn background_keys(
+    mut hotkeys: Hotkeys,
+    mut backgrounds: ResMut<Backgrounds>,
+    backdrop: Single<(&MeshMaterial3d<StandardMaterial>, &mut Visibility), With<Backdrop>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let (material, mut visibility) = backdrop.into_inner();
+    for event in hotkeys.read() {
+        if event.repeat || event.state == ButtonState::Released || event.key_code != KeyCode::KeyB {
+            continue;
+        }
+
+        let next = match backgrounds.current {
+            None => 0,
+            Some(index) => index + 1,
+        };
+        backgrounds.current = (next < backgrounds.list.len()).then_some(next);
+        match backgrounds.current {
+            Some(index) => {
+                let background = &backgrounds.list[index];
+                info!("{} background", background.name);
+                show(background, &mut visibility, material, &mut materials);
+            }
+            None => {
+                info!("No background");
+                *visibility = Visibility::Hidden;
+            }
+        }
+    }
+}
+
+fn show(
+    background: &Background,
+    visibility: &mut Visibility,
+    material: &MeshMaterial3d<StandardMaterial>,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    *visibility = Visibility::Inherited;
+    if let Some(material) = materials.get_mut(material) {
+        material.base_color = background.color;
+        material.base_color_texture = background.image.clone();
+    }
+}
+
+/// Adds a PNG dropped onto the window to the backgrounds and shows it
+#[cfg(not(target_arch = "wasm32"))]
+fn drop_background(
+    mut drops: MessageReader<FileDragAndDrop>,
+    mut backgrounds: ResMut<Backgrounds>,
+    mut images: ResMut<Assets<Image>>,
+    backdrop: Single<(&MeshMaterial3d<StandardMaterial>, &mut Visibility), With<Backdrop>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    use bevy::image::{CompressedImageFormats, ImageType};
+
+    let (material, mut visibility) = backdrop.into_inner();
+    for drop in drops.read() {
+        let FileDragAndDrop::DroppedFile { path_buf, .. } = drop else {
+            continue;
+        };
+        let extension = path_buf
+            .extension()
+            .map(|extension| extension.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let image = std::fs::read(path_buf)
+            .map_err(BevyError::from)
+            .and_then(|bytes| {
+                Ok(Image::from_buffer(
+                    &bytes,
+                    ImageType::Extension(&extension),
+                    CompressedImageFormats::NONE,
+                    true,
+                    ImageSampler::linear(),
+                    RenderAssetUsages::default(),
+                )?)
+            });
+        let image = match image {
+            Ok(image) => image,
+            Err(err) => {
+                warn!("Can't use {} as a background: {err}", path_buf.display());
+                continue;
+            }
+        };
+
+        let background = Background {
+            name: path_buf
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            color: Color::WHITE,
+            image: Some(images.add(image)),
+            fit: Fit::Cover,
+        };
+        info!("{} background", background.name);
+        show(&background, &mut visibility, material, &mut materials);
+        backgrounds.list.push(background);
+        backgrounds.current = Some(backgrounds.list.len() - 1);
+    }
+}
+
+/// Sizes the backdrop to fill the view, and lays its image out on it
+#[allow(clippy::type_complexity)]
+fn fit_backdrop(
+    backgrounds: Res<Backgrounds>,
+    camera: Single<(&Projection, &Transform), (With<Camera3d>, Without<FocusCamera>)>,
+    backdrop: Single<
+        (&mut Transform, &MeshMaterial3d<StandardMaterial>),
+        (With<Backdrop>, Without<Camera3d>),
+    >,
+    images: Res<Assets<Image>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Some(background) = backgrounds.current.map(|index| &backgrounds.list[index]) else {
+        return;
+    };
+    let (projection, camera_transform) = *camera;
+    let Projection::Perspective(perspective) = projection else {
+        return;
+    };
+    let (mut transform, material) = backdrop.into_inner();
+
+    let distance = camera_transform.translation.z - BACKGROUND_Z;
+    let height = 2.0 * distance * (perspective.fov / 2.0).tan();
+    let size = Vec2::new(height * perspective.aspect_ratio, height);
+    transform.scale = size.extend(1.0);
+
+    let uv_transform = match background.fit {
+        Fit::Stretch => Affine2::IDENTITY,
+        Fit::Repeat(texel) => Affine2::from_scale(size / texel / 2.0),
+        Fit::Cover => {
+            let Some(image) = background
+                .image
+                .as_ref()
+                .and_then(|image| images.get(image))
+            else {
+                return;
+            };
+            let image_aspect = image.aspect_ratio().ratio();
+            let view_aspect = size.x / size.y;
+            let scale = if view_aspect > image_aspect {
+                Vec2::new(1.0, image_aspect / view_aspect)
+            } else {
+                Vec2::new(view_aspect / image_aspect, 1.0)
+            };
+            Affine2::from_scale_angle_translation(scale, 0.0, (Vec2::ONE - scale) / 2.0)
+        }
+    };
+    if materials
+        .get(material)
+        .is_some_and(|material| material.uv_transform != uv_transform)
+    {
+        materials.get_mut(material).unwrap().uv_transform = uv_transform;
+    }
+}
@@ -0,0 +1,215 @@
+use bevy::{
+    camera::visibility::RenderLayers,
+    input::{
+        gestures::PinchGesture,
+        mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit},
+        ButtonState,
+    },
+    prelude::*,
+    ui::RelativeCursorPosition,
+};
+
+use crate::{
+    grid::{layout_tiles, Previewed},
+    search::Hotkeys,
+    Tile,
+};
+
+/// The focused previews are drawn by their own camera on this layer, so moving
+/// it leaves the grid where it is
+pub const FOCUS_LAYER: usize = 1;
+/// Radians per pixel dragged
+const ORBIT_SPEED: f32 = 0.01;
+/// Radians per second with the stick all the way over
+const STICK_SPEED: f32 = 2.0;
+const STICK_DEAD_ZONE: f32 = 0.1;
+/// World units per pixel dragged, at the grid camera's distance
+const PAN_SPEED: f32 = 0.0025;
+/// Change in distance per line scrolled
+const ZOOM_SPEED: f32 = 0.1;
+/// Pixels in a line, for mice and trackpads that scroll by pixel
+const SCROLL_LINE: f32 = 20.0;
+const MIN_ZOOM: f32 = 0.2;
+const MAX_ZOOM: f32 = 3.0;
+/// Stops short of looking straight down, where yaw flips
+const MAX_PITCH: f32 = 1.5;
+
+/// Orbits, zooms and pans the view of the focused tile, without moving the grid
+///
+/// Drag with the left mouse button or use the right stick to orbit, scroll or
+/// pinch to zoom, drag with the middle mouse button to pan. R puts the view
+/// back.
+pub struct OrbitCameraPlugin;
+
+impl Plugin for OrbitCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Orbit>()
+            .add_systems(Startup, spawn_focus_camera)
+            .add_systems(
+                Update,
+                (
+                    orbit_input,
+                    (focus_layers, move_focus_camera).after(layout_tiles),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Draws the tiles shown at preview size, from wherever it's been orbited to
+#[derive(Debug, Component)]
+pub struct FocusCamera;
+
+/// How far the view of the focused tile is from the grid camera's
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+struct Orbit {
+    yaw: f32,
+    pitch: f32,
+    /// Fraction of the grid camera's distance
+    zoom: f32,
+    /// In world units, across the screen
+    pan: Vec2,
+    /// Whether the current left or middle drag started outside the UI
+    dragging: bool,
+}
+
+impl Default for Orbit {
+    fn default() -> Self {
+        Self {
+            yaw: 0.0,
+            pitch: 0.0,
+            zoom: 1.0,
+            pan: Vec2::ZERO,
+            dragging: false,
+        }
+    }
+}
+
+fn spawn_focus_camera(mut commands: Commands) {
+    commands.spawn((
+        FocusCamera,
+        Camera3d::default(),
+        Camera {
+            // Draws over the grid, the UI goes on top of both
+            order: 1,
+            clear_color: ClearColorConfig::None,
+            ..default()
+        },
+        RenderLayers::layer(FOCUS_LAYER),
+    ));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn orbit_input(
+    mut hotkeys: Hotkeys,
+    mut orbit: ResMut<Orbit>,
+    time: Res<Time>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    motion: Res<AccumulatedMouseMotion>,
+    scroll: Res<AccumulatedMouseScroll>,
+    mut pinches: MessageReader<PinchGesture>,
+    gamepads: Query<&Gamepad>,
+    interactions: Query<&Interaction>,
+    cursors: Query<&RelativeCursorPosition>,
+) {
+    for event in hotkeys.read() {
+        if !event.repeat && event.state == ButtonState::Pressed && event.key_code == KeyCode::KeyR {
+            *orbit = Orbit::default();
+        }
+    }
+
+    // Sliders, buttons and panels keep the mouse to themselves
+    let over_ui = interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None)
+        || cursors.iter().any(RelativeCursorPosition::cursor_over);
+
+    if buttons.any_just_pressed([MouseButton::Left, MouseButton::Middle]) {
+        orbit.dragging = !over_ui;
+    }
+    if orbit.dragging && buttons.pressed(MouseButton::Left) {
+        orbit.yaw -= motion.delta.x * ORBIT_SPEED;
+        orbit.pitch -= motion.delta.y * ORBIT_SPEED;
+    }
+    if orbit.dragging && buttons.pressed(MouseButton::Middle) {
+        // The camera moves the opposite way to the picture
+        let pan = motion.delta * Vec2::new(-1.0, 1.0) * PAN_SPEED * orbit.zoom;
+        orbit.pan += pan;
+    }
+
+    for gamepad in &gamepads {
+        let stick = gamepad.right_stick();
+        if stick.length() > STICK_DEAD_ZONE {
+            orbit.yaw -= stick.x * STICK_SPEED * time.delta_secs();
+            orbit.pitch += stick.y * STICK_SPEED * time.delta_secs();
+        }
+    }
+    orbit.pitch = orbit.pitch.clamp(-MAX_PITCH, MAX_PITCH);
+
+    let mut zoom = 0.0;
+    if !over_ui {
+        zoom -= match scroll.unit {
+            MouseScrollUnit::Line => scroll.delta.y,
+            MouseScrollUnit::Pixel => scroll.delta.y / SCROLL_LINE,
+        } * ZOOM_SPEED;
+    }
+    for PinchGesture(pinch) in pinches.read() {
+        zoom -= pinch;
+    }
+    orbit.zoom = (orbit.zoom * (1.0 + zoom)).clamp(MIN_ZOOM, MAX_ZOOM);
+}
+
+/// Moves the previewed tiles over to the focus camera
+#[allow(clippy::type_complexity)]
+fn focus_layers(
+    mut commands: Commands,
+    tiles: Query<(Entity, Has<Previewed>, Option<&RenderLayers>), With<Tile>>,
+) {
+    for (tile, focused, layers) in &tiles {
+        let on_focus_layer =
+            layers.is_some_and(|layers| layers.intersects(&RenderLayers::layer(FOCUS_LAYER)));
+        match (focused, on_focus_layer) {
+            (true, false) => {
+                commands
+                    .entity(tile)
+                    .insert(RenderLayers::layer(FOCUS_LAYER));
+            }
+            (false, true) => {
+                commands.entity(tile).remove::<RenderLayers>();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Puts the focus camera where the grid camera is, then orbits it around the
+/// focused tiles
+///
+/// Turning and dollying around a point keeps it in the same spot on screen, so
+/// the preview stays where the grid puts it until it's panned.
+#[allow(clippy::type_complexity)]
+fn move_focus_camera(
+    orbit: Res<Orbit>,
+    tiles: Query<(&Transform, &Visibility), (With<Previewed>, Without<FocusCamera>)>,
+    grid_camera: Single<&Transform, (With<Camera3d>, Without<FocusCamera>)>,
+    mut focus_camera: Single<&mut Transform, With<FocusCamera>>,
+) {
+    let focused = tiles
+        .iter()
+        .filter(|(_, visibility)| **visibility != Visibility::Hidden)
+        .map(|(transform, _)| transform.translation)
+        .collect::<Vec<_>>();
+    let center = match focused.len() {
+        0 => Vec3::ZERO,
+        count => focused.iter().sum::<Vec3>() / count as f32,
+    };
+
+    let mut transform = **grid_camera;
+    transform.translation = center + (transform.translation - center) * orbit.zoom;
+    transform.rotate_around(
+        center,
+        Quat::from_euler(EulerRot::YXZ, orbit.yaw, orbit.pitch, 0.0),
+    );
+    transform.translation += transform.rotation * orbit.pan.extend(0.0);
+    **focus_camera = transform;
+}
@@ -0,0 +1,345 @@
+use bevy::{prelude::*, render::render_resource::ShaderType};
+use serde::Deserialize;
+
+use crate::debug::DebugView;
+
+/// Timing of a single effect, mirrored by `EffectClock` in `effect_time.wgsl`
+///
+/// Shaders read their time from this instead of `globals.time`, so the app
+/// decides when an effect starts, how fast it runs and whether it loops.
+/// `start` is measured on the [`EffectTime`] timeline and `elapsed` is kept up
+/// to date by [`tick_effect_clocks`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, ShaderType, Deserialize)]
+#[serde(default)]
+pub struct EffectClock {
+    /// [`EffectTime`] the effect was (re)started at, in seconds
+    #[serde(skip)]
+    #[reflect(skip_serializing)]
+    pub start: f32,
+    /// Seconds since `start`, scaled by `speed`
+    #[serde(skip)]
+    #[reflect(skip_serializing)]
+    pub elapsed: f32,
+    pub speed: f32,
+    /// Length of one cycle, in seconds
+    pub cycle: f32,
+    /// Non-zero repeats the cycle, zero plays it once and holds the last frame
+    pub looping: u32,
+    /// Index of the [`DebugView`] the material is drawn with, which is also its
+    /// pipeline key
+    #[serde(skip)]
+    #[reflect(skip_serializing)]
+    debug_view: u32,
+    // Padding for WASM, uniforms need to be a multiple of 16 bytes
+    #[serde(skip)]
+    #[reflect(skip_serializing)]
+    _padding: f32,
+}
+
+impl EffectClock {
+    /// A looping clock at normal speed
+    pub const fn with_cycle(cycle: f32) -> Self {
+        Self {
+            start: 0.0,
+            elapsed: 0.0,
+            speed: 1.0,
+            cycle,
+            looping: 1,
+            debug_view: 0,
+            _padding: 0.0,
+        }
+    }
+
+    /// A clock that plays a single cycle once [triggered](Self::trigger)
+    pub const fn one_shot(cycle: f32) -> Self {
+        Self {
+            looping: 0,
+            ..Self::with_cycle(cycle)
+        }
+    }
+
+    /// Plays the effect from the beginning
+    pub fn restart(&mut self, now: f32) {
+        self.start = now;
+        self.elapsed = 0.0;
+    }
+
+    /// Plays a single cycle from the beginning, then holds the last frame
+    pub fn trigger(&mut self, now: f32) {
+        self.looping = 0;
+        self.restart(now);
+    }
+
+    /// Brings `elapsed` up to date with the [`EffectTime`] `now`
+    pub fn tick(&mut self, now: f32) {
+        self.elapsed = (now - self.start).max(0.0) * self.speed;
+    }
+
+    /// Seconds into the current cycle, like `cycle_time` in `effect_time.wgsl`
+    pub fn cycle_time(&self) -> f32 {
+        if self.looping == 0 {
+            return self.elapsed.min(self.cycle);
+        }
+        (self.elapsed / self.cycle).fract() * self.cycle
+    }
+
+    /// Progress through the current cycle from 0 to 1, like `cycle_progress` in
+    /// `effect_time.wgsl`
+    pub fn cycle_progress(&self) -> f32 {
+        self.cycle_time() / self.cycle
+    }
+
+    pub fn debug_view(&self) -> DebugView {
+        DebugView::ALL
+            .get(self.debug_view as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn set_debug_view(&mut self, view: DebugView) {
+        self.debug_view = view as u32;
+    }
+
+    /// How many cycles have completed, like `cycle_index` in `effect_time.wgsl`
+    pub fn cycle_index(&self) -> f32 {
+        if self.looping == 0 {
+            return 0.0;
+        }
+        (self.elapsed / self.cycle).floor()
+    }
+}
+
+impl Default for EffectClock {
+    fn default() -> Self {
+        Self::with_cycle(1.0)
+    }
+}
+
+/// A material driven by an [`EffectClock`]
+///
+/// `#[derive(ShaderMaterial)]` implements this for the field marked `#[clock]`.
+pub trait EffectMaterial: Material {
+    fn clock(&self) -> &EffectClock;
+    fn clock_mut(&mut self) -> &mut EffectClock;
+}
+
+/// Reads and writes the clock of whichever effect material an entity uses
+///
+/// Lets code that doesn't know the material's type, like the gallery's playback
+/// controls, inspect and restart an effect.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct ClockAccess {
+    get: fn(&World, Entity) -> Option<EffectClock>,
+    set: fn(&mut World, Entity, EffectClock),
+}
+
+impl ClockAccess {
+    pub fn of<M: EffectMaterial>() -> Self {
+        Self {
+            get: |world, entity| {
+                let handle = world.get::<MeshMaterial3d<M>>(entity)?;
+                let material = world.resource::<Assets<M>>().get(handle)?;
+                Some(*material.clock())
+            },
+            set: |world, entity, clock| {
+                let Some(handle) = world.get::<MeshMaterial3d<M>>(entity).cloned() else {
+                    return;
+                };
+                if let Some(material) = world.resource_mut::<Assets<M>>().get_mut(&handle) {
+                    *material.clock_mut() = clock;
+                }
+            },
+        }
+    }
+
+    pub fn get(&self, world: &World, entity: Entity) -> Option<EffectClock> {
+        (self.get)(world, entity)
+    }
+
+    pub fn set(&self, world: &mut World, entity: Entity, clock: EffectClock) {
+        (self.set)(world, entity, clock);
+    }
+}
+
+/// The timeline every [`EffectClock`] runs on
+///
+/// Follows [`Time`] while playing, but can be paused, slowed down, sped up and
+/// moved around freely, which scales and stops every effect at once.
+///
+/// With a [`FrameClock`] it follows a frame counter instead, so that every
+/// frame always renders the same way no matter how it was reached. Rollback
+/// netcode can then re-simulate frames and get identical effects.
+#[derive(Debug, Resource)]
+pub struct EffectTime {
+    /// Seconds since the app started, as far as effects are concerned
+    pub elapsed: f32,
+    /// Multiplies how fast `elapsed` follows real time
+    pub speed: f32,
+    pub paused: bool,
+    /// Set by the app to drive effects from its own frame counter
+    pub frames: Option<FrameClock>,
+}
+
+impl EffectTime {
+    /// Length of a frame when stepping through effects
+    pub const FRAME: f32 = 1.0 / 60.0;
+
+    /// A timeline that follows the app's frame counter, see [`FrameClock`]
+    pub fn frame_locked(fps: u32) -> Self {
+        Self {
+            frames: Some(FrameClock { frame: 0, fps }),
+            ..default()
+        }
+    }
+
+    /// Moves the timeline by whole frames, backwards if `frames` is negative
+    pub fn step(&mut self, frames: i32) {
+        match &mut self.frames {
+            Some(clock) => {
+                clock.frame = clock.frame.saturating_add_signed(frames.into());
+                self.elapsed = clock.seconds();
+            }
+            None => self.elapsed = (self.elapsed + frames as f32 * Self::FRAME).max(0.0),
+        }
+    }
+
+    /// Jumps to a point on the timeline, rounded to a frame when frame locked
+    pub fn seek(&mut self, seconds: f32) {
+        let seconds = seconds.max(0.0);
+        match &mut self.frames {
+            Some(clock) => {
+                clock.frame = (seconds * clock.fps as f32).round() as u64;
+                self.elapsed = clock.seconds();
+            }
+            None => self.elapsed = seconds,
+        }
+    }
+}
+
+impl Default for EffectTime {
+    fn default() -> Self {
+        Self {
+            elapsed: 0.0,
+            speed: 1.0,
+            paused: false,
+            frames: None,
+        }
+    }
+}
+
+/// An integer frame counter at a fixed rate, for deterministic effects
+///
+/// The app advances `frame` itself, once per simulated frame, and sets it back
+/// when rolling back. Time is computed from the frame number alone rather than
+/// accumulated, so frame N always gives the same uniforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameClock {
+    pub frame: u64,
+    pub fps: u32,
+}
+
+impl FrameClock {
+    /// Seconds at the start of the current frame
+    pub fn seconds(&self) -> f32 {
+        (self.frame as f64 / self.fps as f64) as f32
+    }
+}
+
+/// Runs every [`tick_effect_clocks`], after [`EffectTime`] has advanced
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SystemSet)]
+pub struct TickEffectClocks;
+
+pub fn advance_effect_time(time: Res<Time>, mut effect_time: ResMut<EffectTime>) {
+    if let Some(frames) = effect_time.frames {
+        effect_time.elapsed = frames.seconds();
+    } else if !effect_time.paused {
+        effect_time.elapsed += time.delta_secs() * effect_time.speed;
+    }
+}
+
+/// Advances the clock of every `M`
+pub fn tick_effect_clocks<M: EffectMaterial>(
+    time: Res<EffectTime>,
+    mut materials: ResMut<Assets<M>>,
+) {
+    for (_, material) in materials.iter_mut() {
+        material.clock_mut().tick(time.elapsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::render_resource::encase::UniformBuffer;
+
+    use super::*;
+    use crate::materials::SparksMaterial;
+
+    const FPS: u32 = 60;
+    const TRIGGER_FRAME: u64 = 37;
+
+    /// Plays through `frames` in order and returns the clock uniform as uploaded
+    /// on the last one
+    fn uniform_after(frames: impl IntoIterator<Item = u64>) -> Vec<u8> {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .insert_resource(EffectTime::frame_locked(FPS))
+            .init_resource::<Assets<SparksMaterial>>()
+            .add_systems(
+                Update,
+                (advance_effect_time, tick_effect_clocks::<SparksMaterial>).chain(),
+            );
+
+        // An effect triggered partway, running at an awkward speed
+        let mut clock = EffectClock::one_shot(3.0);
+        clock.speed = 1.3;
+        clock.trigger(
+            FrameClock {
+                frame: TRIGGER_FRAME,
+                fps: FPS,
+            }
+            .seconds(),
+        );
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<SparksMaterial>>()
+            .add(SparksMaterial { clock, ..default() });
+
+        for frame in frames {
+            let mut time = app.world_mut().resource_mut::<EffectTime>();
+            time.frames.as_mut().unwrap().frame = frame;
+            app.update();
+        }
+
+        let clock = *app
+            .world()
+            .resource::<Assets<SparksMaterial>>()
+            .get(&handle)
+            .unwrap()
+            .clock();
+        let mut buffer = UniformBuffer::new(Vec::new());
+        buffer.write(&clock).unwrap();
+        buffer.into_inner()
+    }
+
+    #[test]
+    fn frame_uniforms_are_bit_identical() {
+        let frame = 100;
+        let played = uniform_after(0..=frame);
+
+        assert_eq!(
+            played,
+            uniform_after([frame]),
+            "jumping straight to the frame"
+        );
+        assert_eq!(played, uniform_after(0..=frame), "playing it again");
+
+        // Run ahead, roll back and re-simulate up to the same frame
+        let rollback = (0..=frame + 20).chain(frame - 10..=frame);
+        assert_eq!(played, uniform_after(rollback), "after a rollback");
+    }
+
+    #[test]
+    fn frames_differ() {
+        assert_ne!(uniform_after([80]), uniform_after([81]));
+    }
+}
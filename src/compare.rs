@@ -0,0 +1,127 @@
+use bevy::{input::ButtonState, prelude::*};
+use bevy_shader_testing::clock::{ClockAccess, EffectTime};
+
+use crate::{search::Hotkeys, selected_tile};
+
+/// Side by side, across the middle of the screen
+const PINNED_POS: Vec3 = Vec3::new(-0.8, -0.1, 0.0);
+const SELECTED_POS: Vec3 = Vec3::new(0.8, -0.1, 0.0);
+/// Where both go when shown one at a time
+const OVERLAY_POS: Vec3 = Vec3::new(0.0, -0.1, 0.0);
+
+/// Shows the selected tile at full size next to a pinned one, with their clocks
+/// started together
+///
+/// X pins the selected tile, or stops comparing. Selecting other tiles then
+/// changes what it's compared to. V switches between side by side and showing
+/// one at a time in the same spot, where Tab flips between the two.
+pub struct ComparePlugin;
+
+impl Plugin for ComparePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Compare>()
+            .add_systems(Update, (compare_keys, sync_clocks).chain());
+    }
+}
+
+#[derive(Debug, Default, Resource)]
+pub struct Compare(pub Option<Comparison>);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Comparison {
+    pinned: Entity,
+    /// Both in the same spot, one at a time
+    overlay: bool,
+    /// Which of the two is shown in the overlay
+    showing_pinned: bool,
+}
+
+impl Comparison {
+    /// Where a tile is shown, or `None` if it's hidden while comparing
+    pub fn placement(&self, tile: Entity, selected: bool) -> Option<Vec3> {
+        let pinned = tile == self.pinned;
+        if !self.overlay {
+            return match (pinned, selected) {
+                (true, _) => Some(PINNED_POS),
+                (false, true) => Some(SELECTED_POS),
+                (false, false) => None,
+            };
+        }
+
+        let shown = match (pinned, selected) {
+            // Comparing a tile to itself shows it either way
+            (true, true) => true,
+            (true, false) => self.showing_pinned,
+            (false, true) => !self.showing_pinned,
+            (false, false) => false,
+        };
+        shown.then_some(OVERLAY_POS)
+    }
+}
+
+fn compare_keys(mut commands: Commands, mut hotkeys: Hotkeys, mut compare: ResMut<Compare>) {
+    for event in hotkeys.read() {
+        if event.repeat || event.state == ButtonState::Released {
+            continue;
+        }
+
+        match (event.key_code, &mut compare.0) {
+            (KeyCode::KeyX, Some(_)) => {
+                info!("Stopped comparing");
+                compare.0 = None;
+            }
+            (KeyCode::KeyX, None) => commands.queue(pin_selected),
+            (KeyCode::KeyV, Some(comparison)) => comparison.overlay = !comparison.overlay,
+            (KeyCode::Tab, Some(comparison)) if comparison.overlay => {
+                comparison.showing_pinned = !comparison.showing_pinned;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn pin_selected(world: &mut World) {
+    let Some(tile) = selected_tile(world) else {
+        return;
+    };
+    info!("Pinned the selected tile, select another to compare");
+    world.resource_mut::<Compare>().0 = Some(Comparison {
+        pinned: tile,
+        overlay: false,
+        showing_pinned: false,
+    });
+}
+
+/// Starts both clocks over together whenever the pair changes, so they line
+/// up frame for frame
+fn sync_clocks(world: &mut World, mut synced: Local<Option<(Entity, Entity)>>) {
+    let Some(comparison) = world.resource::<Compare>().0 else {
+        *synced = None;
+        return;
+    };
+    if world.get_entity(comparison.pinned).is_err() {
+        // Despawned by a reload
+        world.resource_mut::<Compare>().0 = None;
+        return;
+    }
+    let Some(selected) = selected_tile(world) else {
+        return;
+    };
+
+    let pair = (comparison.pinned, selected);
+    if *synced == Some(pair) {
+        return;
+    }
+    *synced = Some(pair);
+
+    let now = world.resource::<EffectTime>().elapsed;
+    for tile in [comparison.pinned, selected] {
+        let Some(access) = world.get::<ClockAccess>(tile).copied() else {
+            continue;
+        };
+        if let Some(mut clock) = access.get(world, tile) {
+            clock.restart(now);
+            access.set(world, tile, clock);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Debug views that replace the color of every effect at once
+//!
+//! Set the [`DebugView`] resource and it's copied into the clock of every
+//! registered material, which is then specialized again with the matching
+//! shader def.
+//! `debug.wgsl` turns that into alpha, UVs, normalized coordinates or a magenta
+//! NaN check instead of the effect.
+
+use bevy::{prelude::*, render::render_resource::RenderPipelineDescriptor};
+
+use crate::clock::EffectMaterial;
+
+/// What the effects draw instead of their color
+///
+/// Also the pipeline key of the materials with `#[bind_group_data(DebugView)]`,
+/// read from their clock, so each view gets its own pipelines.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Resource, Reflect)]
+pub enum DebugView {
+    #[default]
+    Off,
+    /// Alpha as grayscale, opaque
+    Alpha,
+    /// The mesh UVs in red and green
+    Uv,
+    /// The -1 to 1 space the effects draw in, blue outside the unit circle
+    NormCoord,
+    /// Magenta wherever a channel is NaN or infinite
+    NonFinite,
+}
+
+impl DebugView {
+    pub const ALL: [DebugView; 5] = [
+        DebugView::Off,
+        DebugView::Alpha,
+        DebugView::Uv,
+        DebugView::NormCoord,
+        DebugView::NonFinite,
+    ];
+
+    /// The one after this, wrapping around to `Off`
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DebugView::Off => "Off",
+            DebugView::Alpha => "Alpha",
+            DebugView::Uv => "UV",
+            DebugView::NormCoord => "Normalized coordinates",
+            DebugView::NonFinite => "NaN and infinity",
+        }
+    }
+
+    /// The def `debug.wgsl` looks for
+    pub fn shader_def(self) -> Option<&'static str> {
+        match self {
+            DebugView::Off => None,
+            DebugView::Alpha => Some("DEBUG_ALPHA"),
+            DebugView::Uv => Some("DEBUG_UV"),
+            DebugView::NormCoord => Some("DEBUG_NORM_COORD"),
+            DebugView::NonFinite => Some("DEBUG_NON_FINITE"),
+        }
+    }
+
+    /// Adds the shader def to a material's fragment stage, from `Material::specialize`
+    pub fn specialize(self, descriptor: &mut RenderPipelineDescriptor) {
+        if let (Some(def), Some(fragment)) = (self.shader_def(), descriptor.fragment.as_mut()) {
+            fragment.shader_defs.push(def.into());
+        }
+    }
+}
+
+impl<M: EffectMaterial> From<&M> for DebugView {
+    fn from(material: &M) -> Self {
+        material.clock().debug_view()
+    }
+}
+
+/// Copies the view into every `M` that doesn't have it yet, including ones
+/// added since it changed
+pub fn apply_debug_view<M: EffectMaterial>(view: Res<DebugView>, mut materials: ResMut<Assets<M>>) {
+    let stale = materials
+        .iter()
+        .filter(|(_, material)| material.clock().debug_view() != *view)
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    for id in stale {
+        if let Some(material) = materials.get_mut(id) {
+            material.clock_mut().set_debug_view(*view);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::materials::RippleMaterial;
+
+    #[test]
+    fn the_view_travels_with_the_material() {
+        let mut material = RippleMaterial::default();
+        material.clock_mut().set_debug_view(DebugView::Uv);
+        assert_eq!(DebugView::from(&material), DebugView::Uv);
+
+        // The inspector and presets copy materials this way
+        let copy = material.reflect_clone().unwrap();
+        let copy = copy.downcast_ref::<RippleMaterial>().unwrap();
+        assert_eq!(DebugView::from(copy), DebugView::Uv);
+    }
+}
@@ -0,0 +1,28 @@
+use bevy::{input::ButtonState, prelude::*};
+use bevy_shader_testing::debug::DebugView;
+
+use crate::search::Hotkeys;
+
+/// Switches every effect to a debug view at once, to find edge bleeding and
+/// bad math across the whole gallery
+///
+/// F3 steps through alpha as grayscale, raw UVs, the normalized coordinates the
+/// effects draw in and NaN or infinity in magenta, then back to the effects.
+pub struct DebugViewsPlugin;
+
+impl Plugin for DebugViewsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugView>()
+            .add_systems(Update, debug_view_keys);
+    }
+}
+
+fn debug_view_keys(mut hotkeys: Hotkeys, mut view: ResMut<DebugView>) {
+    for event in hotkeys.read() {
+        if event.repeat || event.state == ButtonState::Released || event.key_code != KeyCode::F3 {
+            continue;
+        }
+        *view = view.next();
+        info!("Debug view: {}", view.name());
+    }
+}
@@ -0,0 +1,216 @@
+use bevy::{
+    asset::{AssetPath, LoadedFolder},
+    mesh::MeshVertexBufferLayoutRef,
+    pbr::{MaterialPipeline, MaterialPipelineKey},
+    prelude::*,
+    render::render_resource::{
+        AsBindGroup, RenderPipelineDescriptor, SpecializedMeshPipelineError,
+    },
+};
+use bevy_shader_testing::{
+    clock::{
+        tick_effect_clocks, ClockAccess, EffectClock, EffectMaterial, EffectTime, TickEffectClocks,
+    },
+    debug::{apply_debug_view, DebugView},
+    registry::{MaterialAccess, ShaderRegistry},
+};
+
+use crate::{labels::TileInfo, Tile, SQUARE_EDGE};
+
+const SHADER_FOLDER: &str = "shaders";
+/// Sorts discovered tiles after the ones from the manifest
+const FIRST_DISCOVERED_TILE: usize = 1 << 16;
+/// Discovered shaders can't say how long they run, so they share one cycle length
+const CYCLE: f32 = 5.0;
+
+/// Shows fragment shaders from `assets/shaders` that no material uses yet
+///
+/// Only shaders without a custom vertex stage, and whose only material binding
+/// is the clock, can be shown, such as the ones based on `template.wgsl`.
+pub struct DiscoverPlugin;
+
+impl Plugin for DiscoverPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MaterialPlugin::<DiscoveredMaterial>::default())
+            .init_resource::<EffectTime>()
+            .add_systems(
+                Update,
+                (
+                    spawn_discovered_tiles,
+                    tick_effect_clocks::<DiscoveredMaterial>.in_set(TickEffectClocks),
+                    apply_debug_view::<DiscoveredMaterial>,
+                ),
+            );
+
+        // Folders can't be listed over http
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(Startup, load_shader_folder);
+    }
+}
+
+/// Material that renders whichever fragment shader it's given, with only a clock bound
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone)]
+#[bind_group_data(DiscoveredMaterialKey)]
+pub struct DiscoveredMaterial {
+    pub shader: Handle<Shader>,
+    #[uniform(0)]
+    pub clock: EffectClock,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DiscoveredMaterialKey {
+    shader: Handle<Shader>,
+    debug_view: DebugView,
+}
+
+impl From<&DiscoveredMaterial> for DiscoveredMaterialKey {
+    fn from(material: &DiscoveredMaterial) -> Self {
+        Self {
+            shader: material.shader.clone(),
+            debug_view: material.clock.debug_view(),
+        }
+    }
+}
+
+impl Material for DiscoveredMaterial {
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader = key.bind_group_data.shader;
+        }
+        key.bind_group_data.debug_view.specialize(descriptor);
+        Ok(())
+    }
+}
+
+impl EffectMaterial for DiscoveredMaterial {
+    fn clock(&self) -> &EffectClock {
+        &self.clock
+    }
+
+    fn clock_mut(&mut self) -> &mut EffectClock {
+        &mut self.clock
+    }
+}
+
+#[derive(Debug, Resource)]
+struct ShaderFolder(Handle<LoadedFolder>);
+
+/// Marks tiles spawned from the shader folder rather than the manifest
+#[derive(Debug, Component)]
+struct Discovered;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_shader_folder(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ShaderFolder(asset_server.load_folder(SHADER_FOLDER)));
+}
+
+/// Respawns the discovered tiles whenever the folder is loaded or a file is added or removed
+#[allow(clippy::too_many_arguments)]
+fn spawn_discovered_tiles(
+    mut commands: Commands,
+    time: Res<EffectTime>,
+    mut events: MessageReader<AssetEvent<LoadedFolder>>,
+    folder: Option<Res<ShaderFolder>>,
+    folders: Res<Assets<LoadedFolder>>,
+    shaders: Res<Assets<Shader>>,
+    registry: Res<ShaderRegistry>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<DiscoveredMaterial>>,
+    discovered: Query<Entity, With<Discovered>>,
+) {
+    let Some(folder) = folder else {
+        return;
+    };
+    let changed = events
+        .read()
+        .filter(|event| {
+            event.is_loaded_with_dependencies(&folder.0) || event.is_modified(&folder.0)
+        })
+        .count()
+        > 0;
+    if !changed {
+        return;
+    }
+    let Some(folder) = folders.get(&folder.0) else {
+        return;
+    };
+
+    for tile in &discovered {
+        commands.entity(tile).despawn();
+    }
+
+    let mut new_shaders = folder
+        .handles
+        .iter()
+        .filter_map(|handle| handle.clone().try_typed::<Shader>().ok())
+        .filter(|handle| {
+            let Some(path) = handle.path() else {
+                return false;
+            };
+            let in_use = registry
+                .fragment_shaders()
+                .any(|used| used.path().ends_with(path.path()));
+            !in_use && shaders.get(handle).is_some_and(is_standalone_fragment)
+        })
+        .collect::<Vec<_>>();
+    new_shaders.sort_by_key(|handle| handle.path().map(|path| path.to_string()));
+
+    let mesh = meshes.add(Rectangle::new(SQUARE_EDGE, SQUARE_EDGE));
+    for (index, shader) in new_shaders.into_iter().enumerate() {
+        let Some(path) = shader.path() else {
+            continue;
+        };
+        info!("Discovered {path}");
+        let info = TileInfo {
+            name: name_from_file(path),
+            description: "Found in the shader folder".to_string(),
+            tags: vec!["discovered".to_string()],
+            shader: Some(TileInfo::source_file(path)),
+        };
+        let mut clock = EffectClock::with_cycle(CYCLE);
+        clock.restart(time.elapsed);
+        commands.spawn((
+            Tile(FIRST_DISCOVERED_TILE + index),
+            Discovered,
+            info,
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(materials.add(DiscoveredMaterial { shader, clock })),
+            ClockAccess::of::<DiscoveredMaterial>(),
+            MaterialAccess::of::<DiscoveredMaterial>(),
+        ));
+    }
+}
+
+/// `smoke_bomb.wgsl` is shown as "Smoke bomb"
+fn name_from_file(path: &AssetPath) -> String {
+    let stem = path
+        .path()
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().replace(['_', '-'], " "))
+        .unwrap_or_default();
+    let mut chars = stem.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Whether the shader only needs what [`DiscoveredMaterial`] provides
+fn is_standalone_fragment(shader: &Shader) -> bool {
+    let source = shader.source.as_str();
+    source.contains("@fragment")
+        && !source.contains("@vertex")
+        && source
+            .lines()
+            .filter(|line| line.contains("MATERIAL_BIND_GROUP"))
+            .all(|line| line.contains("@binding(0) var<uniform> clock: EffectClock"))
+}
@@ -0,0 +1,223 @@
+//! Color ramps sampled by the effects instead of fixed colors
+//!
+//! A ramp is a `GRADIENT_WIDTH` by 1 texture, read from left to right. It's a
+//! 2D texture because WebGL has no 1D ones. The default ramps are built from
+//! color stops when the plugin starts, and any image can replace them,
+//! including PNGs saved from the gallery's gradient editor.
+
+use bevy::{
+    asset::{uuid_handle, RenderAssetUsages},
+    image::ImageSampler,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use serde::Deserialize;
+
+use crate::materials::{BurstMaterial, Jackpot, LightningMaterial, MandelbrotMaterial};
+
+pub const GRADIENT_WIDTH: u32 = 256;
+
+pub const BURST_GRADIENT: Handle<Image> = uuid_handle!("5b0c8a44-0b7d-4a53-93f3-52d1c3b6e0a1");
+pub const JACKPOT_GRADIENT: Handle<Image> = uuid_handle!("c1f0e6f2-6a0e-4d8b-9d5c-0b8e4f8d2a72");
+pub const LIGHTNING_GRADIENT: Handle<Image> = uuid_handle!("8e3b7a0d-2f5c-4c1e-a7d9-6b4f1e2c9d83");
+pub const MANDELBROT_GRADIENT: Handle<Image> = uuid_handle!("2d9f4c61-7b8a-4e3f-b2c5-9a1d0e7f6b94");
+
+/// A color at a position along the ramp, from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Deserialize)]
+pub struct ColorStop {
+    pub position: f32,
+    pub color: LinearRgba,
+}
+
+impl ColorStop {
+    pub const fn new(position: f32, color: LinearRgba) -> Self {
+        Self { position, color }
+    }
+}
+
+/// The color at `position`, blending linearly between the stops around it
+///
+/// Stops have to be sorted by position. Before the first and after the last
+/// stop the ramp keeps their color.
+pub fn sample_stops(stops: &[ColorStop], position: f32) -> LinearRgba {
+    let Some(first) = stops.first() else {
+        return LinearRgba::WHITE;
+    };
+    let after = stops.partition_point(|stop| stop.position <= position);
+    match (
+        after.checked_sub(1).map(|index| stops[index]),
+        stops.get(after),
+    ) {
+        (Some(before), Some(after)) => {
+            let t = (position - before.position) / (after.position - before.position);
+            before.color.mix(&after.color, t)
+        }
+        (Some(last), None) => last.color,
+        (None, _) => first.color,
+    }
+}
+
+/// Renders the stops into a ramp texture
+pub fn gradient_image(stops: &[ColorStop]) -> Image {
+    let data = (0..GRADIENT_WIDTH)
+        .flat_map(|x| {
+            // The end texels get exactly the end stops
+            let position = x as f32 / (GRADIENT_WIDTH - 1) as f32;
+            Srgba::from(sample_stops(stops, position)).to_u8_array()
+        })
+        .collect();
+    let mut image = Image::new(
+        Extent3d {
+            width: GRADIENT_WIDTH,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.sampler = ImageSampler::linear();
+    image
+}
+
+/// Adds the default ramps, matching the colors the effects had before ramps
+pub(crate) fn insert_default_gradients(mut images: ResMut<Assets<Image>>) {
+    let ramps = [
+        (
+            BURST_GRADIENT,
+            vec![
+                ColorStop::new(0.0, LinearRgba::rgb(0.0, 0.0, 0.0)),
+                ColorStop::new(1.0, LinearRgba::rgb(1.0, 0.9, 0.1)),
+            ],
+        ),
+        (
+            JACKPOT_GRADIENT,
+            vec![
+                // Dull green
+                ColorStop::new(0.0, LinearRgba::rgb(0.2, 0.29, 0.17)),
+                // Neon green
+                ColorStop::new(0.5, LinearRgba::rgb(0.2235, 1.0, 0.0784)),
+                ColorStop::new(1.0, LinearRgba::rgb(1.0, 1.0, 1.0)),
+            ],
+        ),
+        (
+            LIGHTNING_GRADIENT,
+            vec![
+                ColorStop::new(0.0, LinearRgba::rgb(1.0, 1.0, 1.0)),
+                ColorStop::new(0.8, LinearRgba::rgb(1.0, 1.0, 1.0)),
+                ColorStop::new(0.8, LinearRgba::rgb(0.3, 0.4, 1.0)),
+                ColorStop::new(1.0, LinearRgba::rgb(0.3, 0.4, 1.0)),
+            ],
+        ),
+        (
+            MANDELBROT_GRADIENT,
+            vec![
+                ColorStop::new(0.0, LinearRgba::new(0.01, 0.02, 0.15, 0.1)),
+                ColorStop::new(1.0, LinearRgba::new(1.0, 1.0, 0.0, 1.0)),
+            ],
+        ),
+    ];
+    for (handle, stops) in ramps {
+        images.insert(&handle, gradient_image(&stops)).unwrap();
+    }
+}
+
+/// A material that samples a color ramp
+pub trait GradientMaterial: Material {
+    fn gradient(&self) -> Option<&Handle<Image>>;
+    fn set_gradient(&mut self, gradient: Handle<Image>);
+}
+
+/// Reads and replaces the ramp of whichever gradient material an entity uses
+///
+/// Added to every entity with a [`GradientMaterial`], so the gallery's
+/// gradient editor can work on any of them.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct GradientAccess {
+    get: fn(&World, Entity) -> Option<Handle<Image>>,
+    set: fn(&mut World, Entity, Handle<Image>),
+}
+
+impl GradientAccess {
+    pub fn of<M: GradientMaterial>() -> Self {
+        Self {
+            get: |world, entity| {
+                let handle = world.get::<MeshMaterial3d<M>>(entity)?;
+                let material = world.resource::<Assets<M>>().get(handle)?;
+                material.gradient().cloned()
+            },
+            set: |world, entity, gradient| {
+                let Some(handle) = world.get::<MeshMaterial3d<M>>(entity).cloned() else {
+                    return;
+                };
+                if let Some(material) = world.resource_mut::<Assets<M>>().get_mut(&handle) {
+                    material.set_gradient(gradient);
+                }
+            },
+        }
+    }
+
+    pub fn get(&self, world: &World, entity: Entity) -> Option<Handle<Image>> {
+        (self.get)(world, entity)
+    }
+
+    pub fn set(&self, world: &mut World, entity: Entity, gradient: Handle<Image>) {
+        (self.set)(world, entity, gradient);
+    }
+}
+
+pub(crate) fn add_gradient_access<M: GradientMaterial>(
+    mut commands: Commands,
+    added: Query<Entity, Added<MeshMaterial3d<M>>>,
+) {
+    for entity in &added {
+        commands.entity(entity).insert(GradientAccess::of::<M>());
+    }
+}
+
+macro_rules! impl_gradient_material {
+    ($($material:ty),*) => {
+        $(
+            impl GradientMaterial for $material {
+                fn gradient(&self) -> Option<&Handle<Image>> {
+                    self.gradient.as_ref()
+                }
+
+                fn set_gradient(&mut self, gradient: Handle<Image>) {
+                    self.gradient = Some(gradient);
+                }
+            }
+        )*
+    };
+}
+
+impl_gradient_material!(
+    BurstMaterial,
+    Jackpot,
+    LightningMaterial,
+    MandelbrotMaterial
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stops_at_the_same_position_make_a_hard_step() {
+        let white = LinearRgba::WHITE;
+        let blue = LinearRgba::rgb(0.3, 0.4, 1.0);
+        let stops = [
+            ColorStop::new(0.0, white),
+            ColorStop::new(0.8, white),
+            ColorStop::new(0.8, blue),
+            ColorStop::new(1.0, blue),
+        ];
+
+        assert_eq!(sample_stops(&stops, 0.79), white);
+        assert_eq!(sample_stops(&stops, 0.8), blue);
+        assert_eq!(sample_stops(&stops, 1.0), blue);
+        assert_eq!(sample_stops(&stops, 1.5), blue);
+        assert_eq!(sample_stops(&stops, -0.5), white);
+    }
+}
@@ -0,0 +1,469 @@
+use bevy::{input::ButtonState, prelude::*, ui::RelativeCursorPosition};
+use bevy_shader_testing::gradient::{gradient_image, sample_stops, ColorStop, GradientAccess};
+
+use crate::{
+    inspector::{slider_fraction, slider_track, CHANNEL_WIDTH, FONT_SIZE, SLIDER_WIDTH},
+    search::Hotkeys,
+    selected_tile, styled_button,
+};
+
+const PREVIEW_STEPS: usize = 64;
+const PREVIEW_WIDTH: f32 = 5.0;
+/// How far a texel can be from the blend between stops, above 8 bit rounding
+const TOLERANCE: f32 = 0.02;
+const MAX_STOPS: usize = 8;
+
+/// Editor for the color ramp of the selected tile, toggled with G
+///
+/// Changes show up live on the tile, which gets a ramp of its own on the first
+/// edit. The ramp is saved as a PNG in `assets/gradients/` for games to load.
+pub struct GradientEditorPlugin;
+
+impl Plugin for GradientEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GradientEditor>()
+            .add_systems(Startup, spawn_panel)
+            .add_systems(
+                Update,
+                (
+                    (editor_keys, drag_stops, editor_buttons),
+                    read_edited,
+                    update_editor,
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Debug, Default, Resource)]
+struct GradientEditor {
+    open: bool,
+    tile: Option<Entity>,
+    /// In the order they're listed, which isn't always the order along the ramp
+    stops: Vec<ColorStop>,
+    /// The ramp made for the tile, once it has been edited
+    image: Option<Handle<Image>>,
+    /// The panel needs respawning for a different tile or number of stops
+    rebuild: bool,
+}
+
+impl GradientEditor {
+    fn sorted_stops(&self) -> Vec<ColorStop> {
+        let mut stops = self.stops.clone();
+        stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        stops
+    }
+}
+
+#[derive(Debug, Component)]
+struct EditorPanel;
+
+/// One step of the ramp preview, at `index / PREVIEW_STEPS`
+#[derive(Debug, Component)]
+struct PreviewStep(usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StopChannel {
+    Position,
+    Red,
+    Green,
+    Blue,
+    Alpha,
+}
+
+#[derive(Debug, Component)]
+struct StopSlider {
+    stop: usize,
+    channel: StopChannel,
+}
+
+#[derive(Debug, Component)]
+struct StopSwatch(usize);
+
+#[derive(Debug, Clone, Copy, Component)]
+enum EditorButton {
+    AddStop,
+    RemoveStop(usize),
+    Save,
+}
+
+impl StopChannel {
+    fn value(self, stop: &ColorStop) -> f32 {
+        match self {
+            StopChannel::Position => stop.position,
+            StopChannel::Red => stop.color.red,
+            StopChannel::Green => stop.color.green,
+            StopChannel::Blue => stop.color.blue,
+            StopChannel::Alpha => stop.color.alpha,
+        }
+    }
+
+    fn value_mut(self, stop: &mut ColorStop) -> &mut f32 {
+        match self {
+            StopChannel::Position => &mut stop.position,
+            StopChannel::Red => &mut stop.color.red,
+            StopChannel::Green => &mut stop.color.green,
+            StopChannel::Blue => &mut stop.color.blue,
+            StopChannel::Alpha => &mut stop.color.alpha,
+        }
+    }
+}
+
+fn spawn_panel(mut commands: Commands) {
+    commands.spawn((
+        EditorPanel,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(10.0),
+            bottom: Val::Px(10.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.6)),
+    ));
+}
+
+/// Reads the stops back out of a ramp
+///
+/// Keeps adding a stop at the texel furthest from the blend between the stops
+/// around it, so ramps made from stops come back as about those stops.
+fn stops_from_image(image: &Image) -> Vec<ColorStop> {
+    let colors = (0..image.width())
+        .map(|x| {
+            image
+                .get_color_at(x, 0)
+                .map(|color| color.to_linear())
+                .unwrap_or(LinearRgba::WHITE)
+        })
+        .collect::<Vec<_>>();
+    let last = colors.len().saturating_sub(1);
+
+    let mut stops = vec![0, last];
+    while stops.len() < MAX_STOPS {
+        let colors = &colors;
+        let furthest = stops
+            .windows(2)
+            .flat_map(|pair| {
+                let (start, end) = (pair[0], pair[1]);
+                (start + 1..end).map(move |x| {
+                    let t = (x - start) as f32 / (end - start) as f32;
+                    let blend = colors[start].mix(&colors[end], t);
+                    let error = (blend.to_vec4() - colors[x].to_vec4()).abs().max_element();
+                    (x, error)
+                })
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match furthest {
+            Some((x, error)) if error > TOLERANCE => {
+                let index = stops.partition_point(|&stop| stop < x);
+                stops.insert(index, x);
+            }
+            _ => break,
+        }
+    }
+
+    stops.dedup();
+    stops
+        .into_iter()
+        .map(|x| ColorStop::new(x as f32 / last.max(1) as f32, colors[x]))
+        .collect()
+}
+
+fn editor_keys(mut hotkeys: Hotkeys, mut editor: ResMut<GradientEditor>) {
+    for event in hotkeys.read() {
+        if event.repeat || event.state == ButtonState::Released || event.key_code != KeyCode::KeyG {
+            continue;
+        }
+        editor.open = !editor.open;
+        editor.rebuild = true;
+    }
+}
+
+/// Follows the selected tile, reading its ramp when it changes
+fn read_edited(world: &mut World) {
+    let tile = selected_tile(world);
+    let mut editor = world.resource_mut::<GradientEditor>();
+    if editor.tile == tile && !editor.rebuild {
+        return;
+    }
+    let changed_tile = editor.tile != tile;
+    editor.tile = tile;
+    editor.rebuild = false;
+    if changed_tile {
+        editor.image = None;
+    }
+
+    let gradient = tile.and_then(|tile| world.get::<GradientAccess>(tile)?.get(world, tile));
+    let stops = gradient
+        .and_then(|gradient| world.resource::<Assets<Image>>().get(&gradient))
+        .map(stops_from_image);
+    let mut editor = world.resource_mut::<GradientEditor>();
+    match stops {
+        Some(stops) if changed_tile || editor.stops.is_empty() => editor.stops = stops,
+        Some(_) => {}
+        None => editor.stops.clear(),
+    }
+    let open = editor.open && !editor.stops.is_empty();
+    let stop_count = editor.stops.len();
+
+    let mut panels = world.query_filtered::<Entity, With<EditorPanel>>();
+    let Ok(panel) = panels.single(world) else {
+        return;
+    };
+    let mut panel = world.entity_mut(panel);
+    panel.despawn_related::<Children>();
+    if let Some(mut node) = panel.get_mut::<Node>() {
+        node.display = match open {
+            true => Display::Flex,
+            false => Display::None,
+        };
+    }
+    if !open {
+        return;
+    }
+    panel.with_children(|panel| spawn_stops(panel, stop_count));
+}
+
+fn spawn_stops(panel: &mut ChildSpawner, stop_count: usize) {
+    let row = || Node {
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(4.0),
+        ..default()
+    };
+    let text = |text: String| (Text::new(text), TextFont::from_font_size(FONT_SIZE));
+
+    panel.spawn(text("Gradient, G to close".to_string()));
+    panel.spawn(row()).with_children(|preview| {
+        for index in 0..PREVIEW_STEPS {
+            preview.spawn((
+                PreviewStep(index),
+                Node {
+                    width: Val::Px(PREVIEW_WIDTH),
+                    height: Val::Px(20.0),
+                    ..default()
+                },
+                BackgroundColor(Color::BLACK),
+            ));
+        }
+    });
+
+    let channel = |stop: usize, channel: StopChannel, width: f32, fill: Color| {
+        (StopSlider { stop, channel }, slider_track(width, fill))
+    };
+    for stop in 0..stop_count {
+        panel.spawn((
+            row(),
+            children![
+                text(format!("{stop}")),
+                channel(stop, StopChannel::Position, SLIDER_WIDTH, Color::WHITE),
+                (
+                    StopSwatch(stop),
+                    Node {
+                        width: Val::Px(14.0),
+                        height: Val::Px(14.0),
+                        ..default()
+                    },
+                ),
+                channel(
+                    stop,
+                    StopChannel::Red,
+                    CHANNEL_WIDTH,
+                    Color::linear_rgb(1.0, 0.2, 0.2)
+                ),
+                channel(
+                    stop,
+                    StopChannel::Green,
+                    CHANNEL_WIDTH,
+                    Color::linear_rgb(0.2, 1.0, 0.2)
+                ),
+                channel(
+                    stop,
+                    StopChannel::Blue,
+                    CHANNEL_WIDTH,
+                    Color::linear_rgb(0.2, 0.2, 1.0)
+                ),
+                channel(stop, StopChannel::Alpha, CHANNEL_WIDTH, Color::WHITE),
+                (styled_button("x"), EditorButton::RemoveStop(stop)),
+            ],
+        ));
+    }
+
+    panel.spawn((
+        row(),
+        children![
+            (styled_button("add stop"), EditorButton::AddStop),
+            (styled_button("save png"), EditorButton::Save),
+        ],
+    ));
+}
+
+fn update_editor(
+    editor: Res<GradientEditor>,
+    mut previews: Query<(&PreviewStep, &mut BackgroundColor), Without<StopSwatch>>,
+    mut swatches: Query<(&StopSwatch, &mut BackgroundColor), Without<PreviewStep>>,
+    sliders: Query<(&StopSlider, &Children)>,
+    mut fills: Query<&mut Node>,
+) {
+    if !editor.is_changed() {
+        return;
+    }
+
+    let stops = editor.sorted_stops();
+    for (PreviewStep(index), mut background) in &mut previews {
+        let position = *index as f32 / (PREVIEW_STEPS - 1) as f32;
+        background.set_if_neq(BackgroundColor(sample_stops(&stops, position).into()));
+    }
+    for (StopSwatch(stop), mut background) in &mut swatches {
+        if let Some(stop) = editor.stops.get(*stop) {
+            background.set_if_neq(BackgroundColor(stop.color.into()));
+        }
+    }
+    for (slider, children) in &sliders {
+        let Some(stop) = editor.stops.get(slider.stop) else {
+            continue;
+        };
+        if let Ok(mut fill) = fills.get_mut(children[0]) {
+            let value = slider.channel.value(stop).clamp(0.0, 1.0);
+            fill.width = Val::Percent(100.0 * value);
+        }
+    }
+}
+
+fn drag_stops(
+    mut commands: Commands,
+    sliders: Query<(&Interaction, &RelativeCursorPosition, &StopSlider)>,
+    mut editor: ResMut<GradientEditor>,
+) {
+    let mut edited = false;
+    for (interaction, cursor, slider) in &sliders {
+        let Some(fraction) = slider_fraction(interaction, cursor) else {
+            continue;
+        };
+        if let Some(stop) = editor.stops.get_mut(slider.stop) {
+            *slider.channel.value_mut(stop) = fraction;
+            edited = true;
+        }
+    }
+    if edited {
+        commands.queue(apply_ramp);
+    }
+}
+
+fn editor_buttons(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &EditorButton), Changed<Interaction>>,
+    mut editor: ResMut<GradientEditor>,
+) {
+    for (interaction, &button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            EditorButton::AddStop => {
+                // Halfway along the widest gap, in the color already there
+                let stops = editor.sorted_stops();
+                let (start, end) = stops
+                    .windows(2)
+                    .map(|pair| (pair[0].position, pair[1].position))
+                    .max_by(|a, b| (a.1 - a.0).total_cmp(&(b.1 - b.0)))
+                    .unwrap_or((0.0, 1.0));
+                let position = (start + end) / 2.0;
+                let color = sample_stops(&stops, position);
+                editor.stops.push(ColorStop::new(position, color));
+                editor.rebuild = true;
+            }
+            EditorButton::RemoveStop(stop) => {
+                // A ramp needs at least one color
+                if editor.stops.len() > 1 && stop < editor.stops.len() {
+                    editor.stops.remove(stop);
+                    editor.rebuild = true;
+                    commands.queue(apply_ramp);
+                }
+            }
+            EditorButton::Save => commands.queue(save_ramp),
+        }
+    }
+}
+
+/// Renders the stops into the tile's ramp, giving it one of its own first
+fn apply_ramp(world: &mut World) {
+    let editor = world.resource::<GradientEditor>();
+    let Some(tile) = editor.tile else {
+        return;
+    };
+    let image = gradient_image(&editor.sorted_stops());
+    let handle = editor.image.clone();
+
+    let mut images = world.resource_mut::<Assets<Image>>();
+    match handle {
+        Some(handle) => {
+            images.insert(&handle, image).ok();
+        }
+        None => {
+            let handle = images.add(image);
+            world.resource_mut::<GradientEditor>().image = Some(handle.clone());
+            if let Some(access) = world.get::<GradientAccess>(tile).copied() {
+                access.set(world, tile, handle);
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_ramp(world: &mut World) {
+    use bevy::asset::io::file::FileAssetReader;
+
+    let editor = world.resource::<GradientEditor>();
+    if editor.stops.is_empty() {
+        return;
+    }
+    let image = gradient_image(&editor.sorted_stops());
+
+    let folder = FileAssetReader::get_base_path()
+        .join("assets")
+        .join("gradients");
+    let Some(path) = (1..)
+        .map(|number| folder.join(format!("gradient-{number}.png")))
+        .find(|path| !path.exists())
+    else {
+        return;
+    };
+    let saved = std::fs::create_dir_all(&folder)
+        .map_err(BevyError::from)
+        .and_then(|()| Ok(image.try_into_dynamic()?.save(&path)?));
+    match saved {
+        Ok(()) => info!("Saved gradient {}", path.display()),
+        Err(err) => warn!("Couldn't save gradient {}: {err}", path.display()),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn save_ramp(_world: &mut World) {
+    warn!("Gradients can't be saved from the browser");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ramps_read_back_as_their_stops() {
+        let stops = [
+            ColorStop::new(0.0, LinearRgba::rgb(0.0, 0.0, 0.0)),
+            ColorStop::new(0.4, LinearRgba::rgb(1.0, 0.5, 0.1)),
+            ColorStop::new(1.0, LinearRgba::new(0.2, 0.3, 1.0, 0.5)),
+        ];
+
+        let read = stops_from_image(&gradient_image(&stops));
+
+        assert_eq!(read.len(), stops.len(), "{read:?}");
+        for (read, stop) in read.iter().zip(&stops) {
+            assert!((read.position - stop.position).abs() < 0.01, "{read:?}");
+            let error = (read.color.to_vec4() - stop.color.to_vec4()).abs();
+            assert!(error.max_element() < TOLERANCE, "{read:?}");
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowResized},
+};
+
+use crate::{
+    camera::FocusCamera, compare::Compare, search::FilteredOut, Blank, Selected, Tile, PREVIEW_POS,
+    PREVIEW_SCALE, SQUARE_EDGE,
+};
+
+/// World units kept clear around the grid
+const MARGIN: f32 = 0.1;
+/// Smaller than this and the tiles are paged instead
+const MIN_TILE: f32 = 0.15;
+/// Few enough tiles to fill the space with don't get any bigger than this
+const MAX_TILE: f32 = 0.4;
+
+/// Lays the tiles out in as many columns as suit the window and the number of
+/// tiles, paging through them when they don't fit
+///
+/// The grid fills the space left of the preview and flows again when the
+/// window is resized or a search changes how many tiles there are. Page Up and
+/// Page Down jump a page, moving off the page with W/A/S/D turns it too.
+pub struct GridPlugin;
+
+impl Plugin for GridPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GridLayout>()
+            .add_systems(Startup, spawn_page_label)
+            .add_systems(Update, (layout_tiles, update_page_label).chain());
+    }
+}
+
+/// On the tiles placed at the preview, the selected one or the two being
+/// compared
+#[derive(Debug, Component)]
+pub struct Previewed;
+
+/// Columns, rows and tile size of the grid, as last fitted to the window
+#[derive(Debug, Clone, Copy, Resource)]
+pub struct GridLayout {
+    pub columns: usize,
+    /// On one page
+    rows: usize,
+    /// Edge of a tile, in world units
+    tile: f32,
+    /// Center of the top left tile
+    origin: Vec3,
+    /// Selectable tiles when it was fitted
+    count: usize,
+}
+
+impl Default for GridLayout {
+    fn default() -> Self {
+        Self {
+            columns: 1,
+            rows: 1,
+            tile: SQUARE_EDGE,
+            origin: Vec3::ZERO,
+            count: 0,
+        }
+    }
+}
+
+impl GridLayout {
+    /// Fits `count` tiles into a rectangle, given by its top left corner and size
+    fn fit(top_left: Vec2, size: Vec2, count: usize) -> Self {
+        // The biggest tiles that show all of them at once
+        let (columns, tile) = (1..=count.max(1))
+            .map(|columns| {
+                let rows = count.div_ceil(columns);
+                (columns, (size.x / columns as f32).min(size.y / rows as f32))
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .unwrap();
+
+        let (tile, columns, rows) = if tile >= MIN_TILE {
+            let tile = tile.min(MAX_TILE);
+            (tile, columns, count.div_ceil(columns).max(1))
+        } else {
+            // Doesn't fit, as many as will at the smallest size on each page
+            let fitting = |length: f32| ((length / MIN_TILE) as usize).max(1);
+            (MIN_TILE, fitting(size.x), fitting(size.y))
+        };
+
+        Self {
+            columns,
+            rows,
+            tile,
+            origin: (top_left + Vec2::new(tile, -tile) / 2.0).extend(0.0),
+            count,
+        }
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.columns * self.rows
+    }
+
+    pub fn pages(&self) -> usize {
+        self.count.div_ceil(self.page_size()).max(1)
+    }
+
+    pub fn page_of(&self, index: usize) -> usize {
+        index / self.page_size()
+    }
+
+    /// Where the tile goes on its page
+    fn position(&self, index: usize) -> Vec3 {
+        let slot = index % self.page_size();
+        let row = (slot / self.columns) as f32;
+        let col = (slot % self.columns) as f32;
+        self.origin + self.tile * Vec3::new(col, -row, 0.0)
+    }
+
+    fn scale(&self) -> f32 {
+        self.tile / SQUARE_EDGE
+    }
+}
+
+/// Fits the grid to the window when needed, then places the tiles on the
+/// selected one's page and moves the selected tile to the preview
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn layout_tiles(
+    mut commands: Commands,
+    mut resized: MessageReader<WindowResized>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Projection, &GlobalTransform), (With<Camera3d>, Without<FocusCamera>)>,
+    mut layout: ResMut<GridLayout>,
+    mut selection: ResMut<Selected>,
+    compare: Res<Compare>,
+    mut tiles: Query<
+        (
+            Entity,
+            &Tile,
+            &mut Transform,
+            &mut Visibility,
+            Has<Previewed>,
+        ),
+        (Without<Blank>, Without<FilteredOut>),
+    >,
+    filtered_out: Query<Entity, (With<Previewed>, With<FilteredOut>)>,
+    blank: Single<(&mut Transform, &mut Visibility), With<Blank>>,
+) {
+    for tile in &filtered_out {
+        commands.entity(tile).remove::<Previewed>();
+    }
+
+    let mut tiles = tiles.iter_mut().collect::<Vec<_>>();
+    if tiles.is_empty() {
+        return;
+    }
+    tiles.sort_by_key(|(_, tile, ..)| tile.0);
+
+    let resized = resized.read().count() > 0;
+    if resized || layout.count != tiles.len() {
+        let (projection, camera_transform) = *camera;
+        let Projection::Perspective(perspective) = projection else {
+            return;
+        };
+        // What the camera sees where the tiles are, from the window rather than
+        // the projection, which catches up with a resize later in the frame
+        let height = 2.0 * camera_transform.translation().z * (perspective.fov / 2.0).tan();
+        // Before the camera is placed, or while minimized
+        if height <= 0.0 || window.height() <= 0.0 {
+            return;
+        }
+        let view = Vec2::new(height * window.width() / window.height(), height);
+
+        let preview_left = PREVIEW_POS.x - 0.5 * SQUARE_EDGE * PREVIEW_SCALE;
+        let top_left = Vec2::new(-view.x / 2.0, view.y / 2.0) + Vec2::new(MARGIN, -MARGIN);
+        let size = Vec2::new(preview_left - MARGIN - top_left.x, view.y - 2.0 * MARGIN);
+        *layout = GridLayout::fit(top_left, size.max(Vec2::splat(MIN_TILE)), tiles.len());
+    }
+
+    // Tiles may have been removed by a reload
+    if selection.0 >= tiles.len() {
+        selection.0 = tiles.len() - 1;
+    }
+    let Selected(new_selection) = *selection;
+    let page = layout.page_of(new_selection);
+
+    let (mut blank_tf, mut blank_visibility) = blank.into_inner();
+    // The grid makes way for the two previews when comparing
+    blank_visibility.set_if_neq(match compare.0 {
+        Some(_) => Visibility::Hidden,
+        None => Visibility::Inherited,
+    });
+
+    for (index, (entity, _, mut tf, mut visibility, previewed)) in tiles.into_iter().enumerate() {
+        let pos = layout.position(index);
+        let selected = index == new_selection;
+        if selected {
+            blank_tf.translation = pos;
+            blank_tf.scale = Vec3::splat(layout.scale());
+        }
+
+        let placement = match compare.0 {
+            Some(comparison) => comparison
+                .placement(entity, selected)
+                .map(|translation| (translation, PREVIEW_SCALE)),
+            None if selected => Some((PREVIEW_POS, PREVIEW_SCALE)),
+            None if layout.page_of(index) == page => Some((pos, layout.scale())),
+            None => None,
+        };
+        // Everything placed while comparing is one of the two previews
+        let at_preview = placement.is_some() && (compare.0.is_some() || selected);
+        match (at_preview, previewed) {
+            (true, false) => {
+                commands.entity(entity).insert(Previewed);
+            }
+            (false, true) => {
+                commands.entity(entity).remove::<Previewed>();
+            }
+            _ => {}
+        }
+        match placement {
+            Some((translation, scale)) => {
+                tf.translation = translation;
+                tf.scale = Vec3::splat(scale);
+                visibility.set_if_neq(Visibility::Inherited);
+            }
+            None => {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Component)]
+struct PageLabel;
+
+fn spawn_page_label(mut commands: Commands) {
+    commands.spawn((
+        PageLabel,
+        Text::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.0),
+            bottom: Val::Px(10.0),
+            display: Display::None,
+            ..default()
+        },
+    ));
+}
+
+fn update_page_label(
+    layout: Res<GridLayout>,
+    selection: Res<Selected>,
+    label: Single<(&mut Text, &mut Node), With<PageLabel>>,
+) {
+    let (mut text, mut node) = label.into_inner();
+    let display = if layout.pages() > 1 {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    if node.display != display {
+        node.display = display;
+    }
+    if display == Display::None {
+        return;
+    }
+
+    let value = format!(
+        "Page {} of {}",
+        layout.page_of(selection.0) + 1,
+        layout.pages()
+    );
+    if text.0 != value {
+        text.0 = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn few_tiles_fit_on_one_page() {
+        let layout = GridLayout::fit(Vec2::new(-2.0, 1.0), Vec2::new(2.0, 2.0), 6);
+
+        assert_eq!(layout.pages(), 1);
+        assert!(layout.columns * layout.rows >= 6);
+        assert!(layout.tile <= MAX_TILE);
+    }
+
+    #[test]
+    fn too_many_tiles_are_paged() {
+        let layout = GridLayout::fit(Vec2::new(-1.0, 0.5), Vec2::new(1.0, 1.0), 200);
+
+        assert_eq!(layout.tile, MIN_TILE);
+        assert_eq!((layout.columns, layout.rows), (6, 6));
+        assert_eq!(layout.pages(), 6);
+        assert_eq!(layout.page_of(36), 1);
+    }
+
+    #[test]
+    fn degenerate_sizes_still_have_a_column() {
+        for size in [Vec2::ZERO, Vec2::new(0.01, 5.0), Vec2::new(5.0, 0.01)] {
+            let layout = GridLayout::fit(Vec2::ZERO, size, 10);
+            assert!(layout.columns >= 1 && layout.rows >= 1, "{layout:?}");
+            assert!(layout.pages() >= 1);
+        }
+        let empty = GridLayout::fit(Vec2::ZERO, Vec2::ONE, 0);
+        assert!(empty.columns >= 1);
+        assert_eq!(empty.pages(), 1);
+    }
+}
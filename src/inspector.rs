@@ -0,0 +1,507 @@
+use std::ops::RangeInclusive;
+
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    reflect::{GetPath, ReflectRef, TypeRegistry},
+    ui::RelativeCursorPosition,
+};
+use bevy_shader_testing::{
+    clock::EffectClock, ranges::ReflectParamRanges, registry::MaterialAccess,
+};
+
+use crate::{selected_tile, styled_button, BORDER_COLOR, PRESSED_BUTTON};
+
+const PANEL_WIDTH: f32 = 380.0;
+pub(crate) const SLIDER_WIDTH: f32 = 160.0;
+pub(crate) const CHANNEL_WIDTH: f32 = 40.0;
+pub(crate) const FONT_SIZE: f32 = 14.0;
+const SCROLL_LINE: f32 = 20.0;
+
+/// Side panel for editing the selected tile's material while it runs
+///
+/// The fields are found through `Reflect`, so any material gets a slider for
+/// each `f32`, across its `#[range]` when it has one, a stepper for each
+/// integer and a color picker for each `LinearRgba`, including the ones nested
+/// in parameter structs and vectors.
+/// The clock is left to the playback controls.
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Inspected>()
+            .add_systems(Startup, spawn_panel)
+            .add_systems(
+                Update,
+                (
+                    (drag_sliders, step_fields, scroll_panel),
+                    read_inspected,
+                    update_inspector,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// The tile shown in the panel, and a copy of its material as of this frame
+#[derive(Debug, Default, Resource)]
+struct Inspected {
+    tile: Option<Entity>,
+    material: Option<Box<dyn Reflect>>,
+}
+
+impl Inspected {
+    fn field(&self, path: &str) -> Option<&dyn PartialReflect> {
+        self.material.as_deref()?.reflect_path(path).ok()
+    }
+}
+
+#[derive(Debug, Component)]
+struct InspectorPanel;
+
+/// Shows a field's path and value
+#[derive(Debug, Component)]
+struct FieldLabel(String);
+
+/// Sets an `f32` field to where the cursor is between `min` and `max`
+#[derive(Debug, Component)]
+struct FieldSlider {
+    path: String,
+    min: f32,
+    max: f32,
+}
+
+/// Adds `delta` to an integer field
+#[derive(Debug, Component)]
+struct FieldStepper {
+    path: String,
+    delta: i32,
+}
+
+/// Shows the value of a `LinearRgba` field
+#[derive(Debug, Component)]
+struct ColorSwatch(String);
+
+enum FieldKind {
+    Float { min: f32, max: f32 },
+    Int,
+    Color,
+}
+
+fn spawn_panel(mut commands: Commands) {
+    commands.spawn((
+        InspectorPanel,
+        RelativeCursorPosition::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.0),
+            top: Val::Px(10.0),
+            width: Val::Px(PANEL_WIDTH),
+            max_height: Val::Percent(70.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            overflow: Overflow::scroll_y(),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.6)),
+    ));
+}
+
+/// Finds every editable field under `value`, as paths from the material
+///
+/// Sliders span the `#[range]` of their field, or of the field they're part of.
+/// Fields without one get room to go both ways from their starting value.
+fn collect_fields(
+    value: &dyn PartialReflect,
+    path: &str,
+    range: Option<RangeInclusive<f32>>,
+    registry: &TypeRegistry,
+    fields: &mut Vec<(String, FieldKind)>,
+) {
+    let field_path = |name: &str| match path {
+        "" => name.to_string(),
+        _ => format!("{path}.{name}"),
+    };
+
+    if value.try_downcast_ref::<EffectClock>().is_some() {
+        return;
+    }
+    if value.try_downcast_ref::<LinearRgba>().is_some() {
+        fields.push((path.to_string(), FieldKind::Color));
+        return;
+    }
+    if let Some(&float) = value.try_downcast_ref::<f32>() {
+        let (min, max) = match range {
+            Some(range) => (*range.start(), *range.end()),
+            None => {
+                let extent = (float.abs() * 2.0).max(1.0);
+                (if float < 0.0 { -extent } else { 0.0 }, extent)
+            }
+        };
+        fields.push((path.to_string(), FieldKind::Float { min, max }));
+        return;
+    }
+    if value.try_downcast_ref::<i32>().is_some() || value.try_downcast_ref::<u32>().is_some() {
+        fields.push((path.to_string(), FieldKind::Int));
+        return;
+    }
+
+    let ranges = value.try_as_reflect().and_then(|value| {
+        registry
+            .get_type_data::<ReflectParamRanges>(value.type_id())?
+            .get(value)
+    });
+    // A field's own range, else the one it's part of
+    let range_of = |name: &str| {
+        ranges
+            .and_then(|ranges| ranges.range(name))
+            .or(range.clone())
+    };
+
+    match value.reflect_ref() {
+        ReflectRef::Struct(fields_of) => {
+            for (index, field) in fields_of.iter_fields().enumerate() {
+                let name = fields_of.name_at(index).unwrap_or_default();
+                // Padding
+                if name.starts_with('_') {
+                    continue;
+                }
+                collect_fields(field, &field_path(name), range_of(name), registry, fields);
+            }
+        }
+        ReflectRef::TupleStruct(fields_of) => {
+            for (index, field) in fields_of.iter_fields().enumerate() {
+                let name = index.to_string();
+                collect_fields(field, &field_path(&name), range_of(&name), registry, fields);
+            }
+        }
+        ReflectRef::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                collect_fields(
+                    item,
+                    &format!("{path}[{index}]"),
+                    range.clone(),
+                    registry,
+                    fields,
+                );
+            }
+        }
+        ReflectRef::List(items) => {
+            for (index, item) in items.iter().enumerate() {
+                collect_fields(
+                    item,
+                    &format!("{path}[{index}]"),
+                    range.clone(),
+                    registry,
+                    fields,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+fn label(path: &str) -> impl Bundle + use<> {
+    (
+        Text::new(path),
+        TextFont::from_font_size(FONT_SIZE),
+        FieldLabel(path.to_string()),
+    )
+}
+
+fn slider(path: String, min: f32, max: f32, width: f32, fill: Color) -> impl Bundle + use<> {
+    (FieldSlider { path, min, max }, slider_track(width, fill))
+}
+
+/// A bar that fills from the left, its first child is the fill
+pub(crate) fn slider_track(width: f32, fill: Color) -> impl Bundle + use<> {
+    (
+        Interaction::default(),
+        RelativeCursorPosition::default(),
+        Node {
+            width: Val::Px(width),
+            height: Val::Px(14.0),
+            border: UiRect::all(Val::Px(2.0)),
+            ..default()
+        },
+        BorderColor::all(BORDER_COLOR),
+        BackgroundColor(Color::BLACK),
+        children![(
+            Node {
+                width: Val::Percent(0.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(fill),
+        )],
+    )
+}
+
+/// How far along a slider the cursor is dragging it, from 0 to 1
+pub(crate) fn slider_fraction(
+    interaction: &Interaction,
+    cursor: &RelativeCursorPosition,
+) -> Option<f32> {
+    if *interaction != Interaction::Pressed {
+        return None;
+    }
+    // The cursor goes from -0.5 to 0.5 across the slider
+    Some((cursor.normalized?.x + 0.5).clamp(0.0, 1.0))
+}
+
+fn spawn_field(panel: &mut ChildSpawner, path: String, kind: FieldKind) {
+    let row = Node {
+        justify_content: JustifyContent::SpaceBetween,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(8.0),
+        ..default()
+    };
+    let controls = Node {
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(4.0),
+        ..default()
+    };
+
+    match kind {
+        FieldKind::Float { min, max } => {
+            panel.spawn((
+                row,
+                children![
+                    label(&path),
+                    slider(path, min, max, SLIDER_WIDTH, PRESSED_BUTTON)
+                ],
+            ));
+        }
+        FieldKind::Int => {
+            panel.spawn((
+                row,
+                children![
+                    label(&path),
+                    (
+                        controls,
+                        children![
+                            (
+                                styled_button("-"),
+                                FieldStepper {
+                                    path: path.clone(),
+                                    delta: -1,
+                                }
+                            ),
+                            (styled_button("+"), FieldStepper { path, delta: 1 }),
+                        ],
+                    )
+                ],
+            ));
+        }
+        FieldKind::Color => {
+            // Channels go past 1 for HDR colors, but most stay below
+            let channel = |name: &str, fill: Color| {
+                slider(format!("{path}.{name}"), 0.0, 1.0, CHANNEL_WIDTH, fill)
+            };
+            panel.spawn((
+                row,
+                children![
+                    label(&path),
+                    (
+                        controls,
+                        children![
+                            (
+                                ColorSwatch(path.clone()),
+                                Node {
+                                    width: Val::Px(14.0),
+                                    height: Val::Px(14.0),
+                                    border: UiRect::all(Val::Px(2.0)),
+                                    ..default()
+                                },
+                                BorderColor::all(BORDER_COLOR),
+                            ),
+                            channel("red", Color::linear_rgb(1.0, 0.2, 0.2)),
+                            channel("green", Color::linear_rgb(0.2, 1.0, 0.2)),
+                            channel("blue", Color::linear_rgb(0.2, 0.2, 1.0)),
+                            channel("alpha", Color::WHITE),
+                        ],
+                    )
+                ],
+            ));
+        }
+    }
+}
+
+/// Copies the selected tile's material, rebuilding the panel when the tile changes
+fn read_inspected(world: &mut World) {
+    let tile = selected_tile(world);
+    let access = tile.and_then(|tile| Some((tile, *world.get::<MaterialAccess>(tile)?)));
+    let material = access
+        .and_then(|(tile, access)| access.get(world, tile))
+        .and_then(|material| material.reflect_clone().ok());
+
+    let mut inspected = world.resource_mut::<Inspected>();
+    let changed = inspected.tile != tile;
+    inspected.tile = tile;
+    let Some(material) = material else {
+        inspected.material = None;
+        return;
+    };
+    if !changed {
+        inspected.material = Some(material);
+        return;
+    }
+
+    let mut fields = Vec::new();
+    let registry = world.resource::<AppTypeRegistry>().read();
+    collect_fields(
+        material.as_partial_reflect(),
+        "",
+        None,
+        &registry,
+        &mut fields,
+    );
+    drop(registry);
+    world.resource_mut::<Inspected>().material = Some(material);
+
+    let mut panels = world.query_filtered::<Entity, With<InspectorPanel>>();
+    let Ok(panel) = panels.single(world) else {
+        return;
+    };
+    let mut panel = world.entity_mut(panel);
+    panel.despawn_related::<Children>();
+    if let Some(mut node) = panel.get_mut::<Node>() {
+        node.display = match fields.is_empty() {
+            true => Display::None,
+            false => Display::Flex,
+        };
+    }
+    if let Some(mut scroll) = panel.get_mut::<ScrollPosition>() {
+        scroll.0 = Vec2::ZERO;
+    }
+    panel.with_children(|panel| {
+        for (path, kind) in fields {
+            spawn_field(panel, path, kind);
+        }
+    });
+}
+
+fn update_inspector(
+    inspected: Res<Inspected>,
+    mut labels: Query<(&FieldLabel, &mut Text)>,
+    sliders: Query<(&FieldSlider, &Children)>,
+    mut fills: Query<&mut Node>,
+    mut swatches: Query<(&ColorSwatch, &mut BackgroundColor)>,
+) {
+    for (FieldLabel(path), mut text) in &mut labels {
+        let field = inspected.field(path);
+        let value = if let Some(float) = field.and_then(|f| f.try_downcast_ref::<f32>()) {
+            format!("{path}  {float:.3}")
+        } else if let Some(int) = field.and_then(|f| f.try_downcast_ref::<i32>()) {
+            format!("{path}  {int}")
+        } else if let Some(int) = field.and_then(|f| f.try_downcast_ref::<u32>()) {
+            format!("{path}  {int}")
+        } else {
+            path.clone()
+        };
+        text.set_if_neq(Text(value));
+    }
+
+    for (slider, children) in &sliders {
+        let Some(&value) = inspected
+            .field(&slider.path)
+            .and_then(|field| field.try_downcast_ref::<f32>())
+        else {
+            continue;
+        };
+        if let Ok(mut fill) = fills.get_mut(children[0]) {
+            let fraction = ((value - slider.min) / (slider.max - slider.min)).clamp(0.0, 1.0);
+            fill.width = Val::Percent(100.0 * fraction);
+        }
+    }
+
+    for (ColorSwatch(path), mut background) in &mut swatches {
+        if let Some(&color) = inspected
+            .field(path)
+            .and_then(|field| field.try_downcast_ref::<LinearRgba>())
+        {
+            background.set_if_neq(BackgroundColor(color.into()));
+        }
+    }
+}
+
+/// Runs `edit` on a field of the inspected material
+fn edit_field(world: &mut World, path: &str, edit: impl Fn(&mut dyn PartialReflect)) {
+    let Some(tile) = world.resource::<Inspected>().tile else {
+        return;
+    };
+    let Some(access) = world.get::<MaterialAccess>(tile).copied() else {
+        return;
+    };
+    access.edit(world, tile, |material| {
+        if let Ok(field) = material.reflect_path_mut(path) {
+            edit(field);
+        }
+    });
+}
+
+fn drag_sliders(
+    mut commands: Commands,
+    sliders: Query<(&Interaction, &RelativeCursorPosition, &FieldSlider)>,
+) {
+    for (interaction, cursor, slider) in &sliders {
+        let Some(fraction) = slider_fraction(interaction, cursor) else {
+            continue;
+        };
+        let value = slider.min + fraction * (slider.max - slider.min);
+        let path = slider.path.clone();
+        commands.queue(move |world: &mut World| {
+            edit_field(world, &path, |field| {
+                if let Some(float) = field.try_downcast_mut::<f32>() {
+                    *float = value;
+                }
+            });
+        });
+    }
+}
+
+fn step_fields(
+    mut commands: Commands,
+    steppers: Query<(&Interaction, &FieldStepper), Changed<Interaction>>,
+) {
+    for (interaction, stepper) in &steppers {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let (path, delta) = (stepper.path.clone(), stepper.delta);
+        commands.queue(move |world: &mut World| {
+            edit_field(world, &path, |field| {
+                if let Some(int) = field.try_downcast_mut::<i32>() {
+                    *int = int.saturating_add(delta);
+                } else if let Some(int) = field.try_downcast_mut::<u32>() {
+                    *int = int.saturating_add_signed(delta);
+                }
+            });
+        });
+    }
+}
+
+fn scroll_panel(
+    mut wheel: MessageReader<MouseWheel>,
+    mut panel: Query<
+        (&RelativeCursorPosition, &ComputedNode, &mut ScrollPosition),
+        With<InspectorPanel>,
+    >,
+) {
+    let Ok((cursor, node, mut scroll)) = panel.single_mut() else {
+        return;
+    };
+    for event in wheel.read() {
+        if !cursor.cursor_over() {
+            continue;
+        }
+        let lines = match event.unit {
+            MouseScrollUnit::Line => event.y * SCROLL_LINE,
+            MouseScrollUnit::Pixel => event.y,
+        };
+        let max = (node.content_size().y - node.size().y).max(0.0) * node.inverse_scale_factor();
+        scroll.0.y = (scroll.0.y - lines).clamp(0.0, max);
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::{asset::AssetPath, prelude::*};
+
+use crate::{camera::FocusCamera, search::FilteredOut, Selected, Tile, SQUARE_EDGE};
+
+const LABEL_FONT_SIZE: f32 = 12.0;
+/// Space between a tile and its label
+const LABEL_GAP: f32 = 2.0;
+
+/// Names every tile, and shows the focused one's description, tags and shader
+/// file above the preview
+pub struct LabelsPlugin;
+
+impl Plugin for LabelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_header).add_systems(
+            Update,
+            (spawn_labels, (place_labels, update_header)).chain(),
+        );
+    }
+}
+
+/// What the gallery shows about a tile
+#[derive(Debug, Clone, Component)]
+pub struct TileInfo {
+    pub name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    /// The shader's source file, from the crate root
+    pub shader: Option<String>,
+}
+
+impl TileInfo {
+    /// Where the shader at `path` lives in the repository
+    pub fn source_file(path: &AssetPath) -> String {
+        // Embedded shaders are the ones in assets/shaders
+        let path = path.path();
+        let path = path.strip_prefix("bevy_shader_testing").unwrap_or(path);
+        format!("assets/{}", path.display())
+    }
+}
+
+/// The name under a grid tile
+#[derive(Debug, Component)]
+struct TileLabel(Entity);
+
+#[derive(Debug, Component)]
+struct Header;
+
+#[derive(Debug, Component)]
+enum HeaderLine {
+    Name,
+    Description,
+    Tags,
+    Shader,
+}
+
+fn spawn_header(mut commands: Commands) {
+    let line = |line: HeaderLine, font_size: f32, color: Color| {
+        (
+            line,
+            Text::default(),
+            TextFont::from_font_size(font_size),
+            TextColor(color),
+        )
+    };
+    commands.spawn((
+        Header,
+        Node {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(2.0),
+            display: Display::None,
+            ..default()
+        },
+        children![
+            line(HeaderLine::Name, 28.0, Color::WHITE),
+            line(HeaderLine::Description, 16.0, Color::WHITE),
+            line(HeaderLine::Tags, 14.0, Color::srgb(0.6, 0.8, 1.0)),
+            line(HeaderLine::Shader, 14.0, Color::srgb(0.6, 0.6, 0.6)),
+        ],
+    ));
+}
+
+fn spawn_labels(mut commands: Commands, tiles: Query<(Entity, &TileInfo), Added<TileInfo>>) {
+    for (tile, info) in &tiles {
+        commands.spawn((
+            TileLabel(tile),
+            Text::new(info.name.clone()),
+            TextFont::from_font_size(LABEL_FONT_SIZE),
+            TextLayout::new_with_justify(Justify::Center),
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+        ));
+    }
+}
+
+/// The corners of a tile's bottom or top edge on screen, ignoring its rotation
+fn screen_edge(
+    camera: (&Camera, &GlobalTransform),
+    transform: &Transform,
+    top: bool,
+) -> Option<(Vec2, Vec2)> {
+    let (camera, camera_transform) = camera;
+    let half = 0.5 * SQUARE_EDGE * transform.scale;
+    let y = if top { half.y } else { -half.y };
+    let left = transform.translation + Vec3::new(-half.x, y, 0.0);
+    let right = transform.translation + Vec3::new(half.x, y, 0.0);
+    Some((
+        camera.world_to_viewport(camera_transform, left).ok()?,
+        camera.world_to_viewport(camera_transform, right).ok()?,
+    ))
+}
+
+/// Keeps the labels under their tiles, and hides the focused one's since the
+/// header names it, as well as those of hidden tiles
+fn place_labels(
+    mut commands: Commands,
+    selected: Res<Selected>,
+    camera: Single<(&Camera, &GlobalTransform), Without<FocusCamera>>,
+    tiles: Query<(&Tile, &Transform, &Visibility, Has<FilteredOut>)>,
+    mut labels: Query<(Entity, &TileLabel, &mut Node)>,
+) {
+    let shown = tiles
+        .iter()
+        .filter(|(.., filtered_out)| !filtered_out)
+        .map(|(tile, ..)| tile.0);
+    let selected_tile = focused_tile(selected.0, shown);
+    for (label, &TileLabel(tile), mut node) in &mut labels {
+        let Ok((&Tile(index), transform, visibility, _)) = tiles.get(tile) else {
+            // Despawned by a reload
+            commands.entity(label).despawn();
+            continue;
+        };
+        let placed = match screen_edge(*camera, transform, false) {
+            Some((left, right))
+                if *visibility != Visibility::Hidden && Some(index) != selected_tile =>
+            {
+                Node {
+                    display: Display::Flex,
+                    left: Val::Px(left.x),
+                    top: Val::Px(left.y + LABEL_GAP),
+                    width: Val::Px(right.x - left.x),
+                    ..node.clone()
+                }
+            }
+            _ => Node {
+                display: Display::None,
+                ..node.clone()
+            },
+        };
+        // Left alone when nothing moved, so the UI isn't laid out again
+        node.set_if_neq(placed);
+    }
+}
+
+fn update_header(
+    selected: Res<Selected>,
+    camera: Single<(&Camera, &GlobalTransform), Without<FocusCamera>>,
+    tiles: Query<(&Tile, &Transform, &Visibility, &TileInfo), Without<FilteredOut>>,
+    mut header: Single<&mut Node, With<Header>>,
+    mut lines: Query<(&HeaderLine, &mut Text)>,
+) {
+    let selected_tile = focused_tile(selected.0, tiles.iter().map(|(tile, ..)| tile.0));
+    let focused = tiles
+        .iter()
+        .find(|(tile, ..)| Some(tile.0) == selected_tile)
+        .filter(|(_, _, visibility, _)| **visibility != Visibility::Hidden)
+        .and_then(|(_, transform, _, info)| Some((info, screen_edge(*camera, transform, true)?)));
+    let Some((info, (left, right))) = focused else {
+        header.display = Display::None;
+        return;
+    };
+    let viewport = camera.0.logical_viewport_size().unwrap_or_default();
+
+    header.display = Display::Flex;
+    header.left = Val::Px(left.x);
+    header.bottom = Val::Px(viewport.y - left.y + LABEL_GAP);
+    header.max_width = Val::Px(right.x - left.x);
+
+    for (line, mut text) in &mut lines {
+        let value = match line {
+            HeaderLine::Name => info.name.clone(),
+            HeaderLine::Description => info.description.clone(),
+            HeaderLine::Tags => info
+                .tags
+                .iter()
+                .map(|tag| format!("#{tag}"))
+                .collect::<Vec<_>>()
+                .join(" "),
+            HeaderLine::Shader => info.shader.clone().unwrap_or_default(),
+        };
+        if text.0 != value {
+            text.0 = value;
+        }
+    }
+}
+
+/// The `Tile` index of the selected tile, counting in grid order
+fn focused_tile(selected: usize, tiles: impl Iterator<Item = usize>) -> Option<usize> {
+    let mut tiles = tiles.collect::<Vec<_>>();
+    tiles.sort();
+    tiles.get(selected).copied()
+}
@@ -0,0 +1,161 @@
+//! The effects from the gallery, packaged for use in games
+//!
+//! Add [`ShaderVfxPlugin`] to the app and spawn meshes with any of the
+//! [`materials`]. The shaders are embedded in the binary, so games don't need
+//! to copy them into their own assets.
+
+use std::path::{Path, PathBuf};
+
+use bevy::{asset::io::embedded::EmbeddedAssetRegistry, prelude::*};
+
+// Lets `#[derive(ShaderMaterial)]` refer to this crate by name from inside it
+extern crate self as bevy_shader_testing;
+
+pub mod clock;
+pub mod debug;
+pub mod gradient;
+pub mod materials;
+pub mod palette;
+pub mod ranges;
+pub mod registry;
+pub mod uniforms;
+
+use clock::{advance_effect_time, EffectTime, TickEffectClocks};
+use gradient::{add_gradient_access, insert_default_gradients};
+use materials::*;
+use palette::apply_palette;
+use registry::RegisterShaderTile;
+
+/// Embeds `assets/shaders/<name>` as `embedded://bevy_shader_testing/shaders/<name>`,
+/// where the materials and the WGSL imports look for it
+macro_rules! embed_shader {
+    ($app: expr, $name: literal) => {{
+        let (watched, embedded) = shader_paths($name);
+        $app.world_mut()
+            .resource_mut::<EmbeddedAssetRegistry>()
+            .insert_asset(
+                watched,
+                &embedded,
+                include_bytes!(concat!("../assets/shaders/", $name)),
+            );
+    }};
+}
+
+/// The path the embedded watcher reloads a shader from and the one it's
+/// embedded at
+///
+/// The watcher looks changed files up relative to the crate root, so the first
+/// has to be spelled that way rather than relative to this file.
+fn shader_paths(name: &str) -> (PathBuf, PathBuf) {
+    (
+        Path::new("assets/shaders").join(name),
+        Path::new("bevy_shader_testing/shaders").join(name),
+    )
+}
+
+/// Registers every material, embeds the shaders they use and runs their clocks
+pub struct ShaderVfxPlugin;
+
+impl Plugin for ShaderVfxPlugin {
+    fn build(&self, app: &mut App) {
+        embed_shader!(app, "helpers.wgsl");
+        embed_shader!(app, "effect_time.wgsl");
+        embed_shader!(app, "debug.wgsl");
+        embed_shader!(app, "bezier-swoosh.wgsl");
+        embed_shader!(app, "bezier.wgsl");
+        embed_shader!(app, "bill-burst.wgsl");
+        embed_shader!(app, "blocking.wgsl");
+        embed_shader!(app, "burst.wgsl");
+        embed_shader!(app, "clink.wgsl");
+        embed_shader!(app, "corner_slash.wgsl");
+        embed_shader!(app, "droplet.wgsl");
+        embed_shader!(app, "edge_slash.wgsl");
+        embed_shader!(app, "fire.wgsl");
+        embed_shader!(app, "focal_lines.wgsl");
+        embed_shader!(app, "fresnel.wgsl");
+        embed_shader!(app, "hitspark.wgsl");
+        embed_shader!(app, "jackpot.wgsl");
+        embed_shader!(app, "lightning.wgsl");
+        embed_shader!(app, "line_field.wgsl");
+        embed_shader!(app, "mandelbrot.wgsl");
+        embed_shader!(app, "multi_ripple_ring.wgsl");
+        embed_shader!(app, "normal-cube.wgsl");
+        embed_shader!(app, "particles.wgsl");
+        embed_shader!(app, "ripple.wgsl");
+        embed_shader!(app, "ripple_ring.wgsl");
+        embed_shader!(app, "rocks.wgsl");
+        embed_shader!(app, "smoke_bomb.wgsl");
+        embed_shader!(app, "sparks.wgsl");
+        embed_shader!(app, "spinner.wgsl");
+        embed_shader!(app, "sugarcoat.wgsl");
+        embed_shader!(app, "vertex.wgsl");
+
+        app.init_resource::<EffectTime>()
+            .add_systems(Startup, insert_default_gradients)
+            .add_systems(Update, advance_effect_time.before(TickEffectClocks))
+            .add_systems(
+                Update,
+                (
+                    apply_palette::<HitSparkMaterial>,
+                    apply_palette::<BlockMaterial>,
+                    apply_palette::<ClinkMaterial>,
+                    apply_palette::<RippleRingMaterial>,
+                    apply_palette::<LineFieldMaterial>,
+                    apply_palette::<MultiRippleRingMaterial>,
+                    add_gradient_access::<BurstMaterial>,
+                    add_gradient_access::<Jackpot>,
+                    add_gradient_access::<LightningMaterial>,
+                    add_gradient_access::<MandelbrotMaterial>,
+                ),
+            );
+
+        app.register_shader_tile::<FresnelMaterial>()
+            .register_shader_tile::<RippleRingMaterial>()
+            .register_shader_tile::<HitSparkMaterial>()
+            .register_shader_tile::<BlockMaterial>()
+            .register_shader_tile::<ClinkMaterial>()
+            .register_shader_tile::<LineFieldMaterial>()
+            .register_shader_tile::<SpinnerMaterial>()
+            .register_shader_tile::<FocalLineMaterial>()
+            .register_shader_tile::<LightningMaterial>()
+            .register_shader_tile::<CornerSlashMaterial>()
+            .register_shader_tile::<EdgeSlashMaterial>()
+            .register_shader_tile::<BurstMaterial>()
+            .register_shader_tile::<RocksMaterial>()
+            .register_shader_tile::<SparksMaterial>()
+            .register_shader_tile::<SmokeBombMaterial>()
+            .register_shader_tile::<VertexTest>()
+            .register_shader_tile::<RippleMaterial>()
+            .register_shader_tile::<Jackpot>()
+            .register_shader_tile::<FireMaterial>()
+            .register_shader_tile::<MultiRippleRingMaterial>()
+            .register_shader_tile::<BezierMaterial>()
+            .register_shader_tile::<BezierSwooshMaterial>()
+            .register_shader_tile::<NormalCubeMaterial>()
+            .register_shader_tile::<SugarCoatMaterial>()
+            .register_shader_tile::<BillBurstMaterial>()
+            .register_shader_tile::<ParticlesMaterial>()
+            .register_shader_tile::<DropletMaterial>()
+            .register_shader_tile::<MandelbrotMaterial>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shaders_are_watched_where_the_watcher_looks() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let changed = root.join("assets/shaders/ripple.wgsl");
+        assert!(changed.exists());
+
+        let (watched, embedded) = shader_paths("ripple.wgsl");
+        // What `EmbeddedWatcher` makes of a change to the file
+        assert_eq!(changed.strip_prefix(root).unwrap(), watched);
+        assert_eq!(
+            embedded,
+            Path::new("bevy_shader_testing/shaders/ripple.wgsl")
+        );
+    }
+}
@@ -1,68 +1,81 @@
-use std::f32::consts::PI;
-
-use bevy::{
-    input::{keyboard::KeyboardInput, ButtonState},
-    prelude::*,
-    window::WindowMode,
-};
-
-mod materials;
-use materials::*;
+use bevy::{input::ButtonState, prelude::*, window::WindowMode};
+use bevy_shader_testing::ShaderVfxPlugin;
+
+mod backgrounds;
+mod camera;
+mod compare;
+mod debug_views;
+mod discover;
+mod gradient_editor;
+mod grid;
+mod inspector;
+mod labels;
+mod manifest;
+mod meshes;
+mod mutate;
+mod palette_picker;
+mod playback;
+#[cfg(not(target_arch = "wasm32"))]
+mod presets;
+mod search;
+mod wireframe;
+
+use backgrounds::BackgroundsPlugin;
+use camera::OrbitCameraPlugin;
+use compare::ComparePlugin;
+use debug_views::DebugViewsPlugin;
+use discover::DiscoverPlugin;
+use gradient_editor::GradientEditorPlugin;
+use grid::{GridLayout, GridPlugin};
+use inspector::InspectorPlugin;
+use labels::LabelsPlugin;
+use manifest::ManifestPlugin;
+use meshes::{mesh_button, MeshesPlugin};
+use mutate::MutatePlugin;
+use palette_picker::PalettePickerPlugin;
+use playback::{playback_controls, PlaybackPlugin};
+use search::{FilteredOut, Hotkeys, SearchPlugin};
+use wireframe::WireframeOverlayPlugin;
 
 fn main() {
-    App::new()
-        .add_plugins((
-            DefaultPlugins.set(WindowPlugin {
-                primary_window: Some(Window {
-                    mode: WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
-                    ..default()
-                }),
+    let mut app = App::new();
+    app.add_plugins((
+        DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                mode: WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
                 ..default()
             }),
-            (
-                MaterialPlugin::<FresnelMaterial>::default(),
-                MaterialPlugin::<RippleRingMaterial>::default(),
-                MaterialPlugin::<HitSparkMaterial>::default(),
-                MaterialPlugin::<BlockMaterial>::default(),
-                MaterialPlugin::<ClinkMaterial>::default(),
-                MaterialPlugin::<LineFieldMaterial>::default(),
-                MaterialPlugin::<SpinnerMaterial>::default(),
-                MaterialPlugin::<FocalLineMaterial>::default(),
-                MaterialPlugin::<LightningMaterial>::default(),
-                MaterialPlugin::<CornerSlashMaterial>::default(),
-                MaterialPlugin::<EdgeSlashMaterial>::default(),
-                MaterialPlugin::<BurstMaterial>::default(),
-                MaterialPlugin::<RocksMaterial>::default(),
-                MaterialPlugin::<SparksMaterial>::default(),
-                MaterialPlugin::<SmokeBombMaterial>::default(),
-            ),
-            (
-                MaterialPlugin::<VertexTest>::default(),
-                MaterialPlugin::<RippleMaterial>::default(),
-                MaterialPlugin::<Jackpot>::default(),
-                MaterialPlugin::<FireMaterial>::default(),
-                MaterialPlugin::<MultiRippleRingMaterial>::default(),
-                MaterialPlugin::<BezierMaterial>::default(),
-                MaterialPlugin::<BezierSwooshMaterial>::default(),
-                MaterialPlugin::<NormalCubeMaterial>::default(),
-                MaterialPlugin::<SugarCoatMaterial>::default(),
-                MaterialPlugin::<BillBurstMaterial>::default(),
-                MaterialPlugin::<ParticlesMaterial>::default(),
-                MaterialPlugin::<DropletMaterial>::default(),
-                MaterialPlugin::<MandelbrotMaterial>::default(),
-            ),
-        ))
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (
-                rotate_meshes,
-                update_selection,
-                keyboard_system,
-                button_system,
-            ),
-        )
-        .run();
+            ..default()
+        }),
+        ShaderVfxPlugin,
+        ManifestPlugin,
+        DiscoverPlugin,
+        PlaybackPlugin,
+        InspectorPlugin,
+        MutatePlugin,
+        PalettePickerPlugin,
+        GradientEditorPlugin,
+        LabelsPlugin,
+        SearchPlugin,
+        ComparePlugin,
+        GridPlugin,
+        // How the preview is shown
+        (
+            MeshesPlugin,
+            OrbitCameraPlugin,
+            BackgroundsPlugin,
+            DebugViewsPlugin,
+            WireframeOverlayPlugin,
+        ),
+    ))
+    .add_systems(Startup, setup)
+    .add_systems(
+        Update,
+        (rotate_meshes, keyboard_system, button_system, button_colors),
+    );
+    #[cfg(not(target_arch = "wasm32"))]
+    app.add_plugins(presets::PresetsPlugin);
+    app.run();
 }
 
 #[derive(Debug, Resource)]
@@ -71,79 +84,29 @@ struct Selected(usize);
 #[derive(Debug, Component)]
 struct Rotate;
 
+/// A gallery tile, ordered by its position in the manifest
+#[derive(Debug, Component)]
+struct Tile(usize);
+
 #[derive(Debug, Component)]
 struct Blank;
 
-#[allow(clippy::type_complexity)]
+/// The selected tile, counting in grid order past the ones filtered out
+fn selected_tile(world: &mut World) -> Option<Entity> {
+    let &Selected(selected) = world.get_resource::<Selected>()?;
+    let mut tiles = world.query_filtered::<(Entity, &Tile), Without<FilteredOut>>();
+    let mut tiles = tiles
+        .iter(world)
+        .map(|(entity, tile)| (tile.0, entity))
+        .collect::<Vec<_>>();
+    tiles.sort_by_key(|(index, _)| *index);
+    tiles.get(selected).map(|&(_, entity)| entity)
+}
+
 fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
-    // This way to get around bevy system param limit
-    (
-        mut standard_materials,
-        mut fresnel_materials,
-        mut ripple_ring_materials,
-        mut explosion_materials,
-        mut block_materials,
-        mut clink_materials,
-        mut line_field_materials,
-        mut spinner_materials,
-        mut focal_line_materials,
-        mut lightning_materials,
-        mut corner_slash_materials,
-        mut edge_slash_materials,
-        mut burst_materials,
-        mut rocks_materials,
-        mut sparks_materials,
-    ): (
-        ResMut<Assets<StandardMaterial>>,
-        ResMut<Assets<FresnelMaterial>>,
-        ResMut<Assets<RippleRingMaterial>>,
-        ResMut<Assets<HitSparkMaterial>>,
-        ResMut<Assets<BlockMaterial>>,
-        ResMut<Assets<ClinkMaterial>>,
-        ResMut<Assets<LineFieldMaterial>>,
-        ResMut<Assets<SpinnerMaterial>>,
-        ResMut<Assets<FocalLineMaterial>>,
-        ResMut<Assets<LightningMaterial>>,
-        ResMut<Assets<CornerSlashMaterial>>,
-        ResMut<Assets<EdgeSlashMaterial>>,
-        ResMut<Assets<BurstMaterial>>,
-        ResMut<Assets<RocksMaterial>>,
-        ResMut<Assets<SparksMaterial>>,
-    ),
-    (
-        mut vertex_materials,
-        mut ripple_materials,
-        mut jackpot_materials,
-        mut fire_materials,
-        mut smoke_bomb_materials,
-        mut multi_ripple_ring_materials,
-        mut bezier_materials,
-        mut bezier_swoosh_materials,
-        mut normal_cube_materials,
-        mut sugarcoat_materials,
-        mut bill_burst_materials,
-        mut particles_materials,
-        mut droplet_materials,
-        mut mandelbrot_materials,
-    ): (
-        ResMut<Assets<VertexTest>>,
-        ResMut<Assets<RippleMaterial>>,
-        ResMut<Assets<Jackpot>>,
-        ResMut<Assets<FireMaterial>>,
-        ResMut<Assets<SmokeBombMaterial>>,
-        ResMut<Assets<MultiRippleRingMaterial>>,
-        ResMut<Assets<BezierMaterial>>,
-        ResMut<Assets<BezierSwooshMaterial>>,
-        ResMut<Assets<NormalCubeMaterial>>,
-        ResMut<Assets<SugarCoatMaterial>>,
-        ResMut<Assets<BillBurstMaterial>>,
-        ResMut<Assets<ParticlesMaterial>>,
-        ResMut<Assets<DropletMaterial>>,
-        ResMut<Assets<MandelbrotMaterial>>,
-    ),
-    asset_server: Res<AssetServer>,
+    mut standard_materials: ResMut<Assets<StandardMaterial>>,
 ) {
     commands.insert_resource(Selected(0));
     commands.spawn((
@@ -151,215 +114,6 @@ fn setup(
         Transform::from_xyz(0.0, 0.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
     ));
 
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(mandelbrot_materials.add(MandelbrotMaterial {})),
-    ));
-
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(droplet_materials.add(DropletMaterial {})),
-    ));
-
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(particles_materials.add(ParticlesMaterial {
-            effect: UVec4::splat(1),
-        })),
-    ));
-
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(particles_materials.add(ParticlesMaterial {
-            effect: UVec4::splat(0),
-        })),
-    ));
-
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(bill_burst_materials.add(BillBurstMaterial {})),
-    ));
-
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(sugarcoat_materials.add(SugarCoatMaterial {})),
-    ));
-
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(normal_cube_materials.add(NormalCubeMaterial {})),
-    ));
-
-    // Even though we don't use the fourth dimension, Bevy wants them as 4d
-    let bezier_swoosh_control_points = vec![
-        Vec3::new(-0.7, 0.9, 2.0),
-        Vec3::new(1.2, 1.0, 8.0),
-        Vec3::new(-0.2, -0.9, 6.0),
-        Vec3::new(-0.9, 0.7, 0.0),
-    ];
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(
-            bezier_swoosh_materials.add(BezierSwooshMaterial {
-                control_points: pad_to(bezier_swoosh_control_points, 16)
-                    .as_slice()
-                    .try_into()
-                    .unwrap(),
-                curves: UVec4::splat(1),
-            }),
-        ),
-    ));
-
-    // Even though we don't use the fourth dimension, Bevy wants them as 4d
-    let bezier_control_points = vec![
-        // Set 1
-        Vec3::new(-0.9, -0.9, 2.0),
-        Vec3::new(1.0, 1.0, 10.0),
-        Vec3::new(-2.0, 1.5, 10.0),
-        Vec3::new(-0.2, -0.2, 5.0),
-        // Set 2 (first anchor is assumed to be the last point of previous curve)
-        // Think of it as auto-continuity
-        Vec3::new(1.6, -1.9, 10.0),
-        Vec3::new(-3.0, 0.2, 10.0),
-        Vec3::new(0.9, 0.0, 2.0),
-    ];
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(
-            bezier_materials.add(BezierMaterial {
-                control_points: pad_to(bezier_control_points, 16)
-                    .as_slice()
-                    .try_into()
-                    .unwrap(),
-                curves: UVec4::splat(2),
-
-                texture: Some(asset_server.load("pictures/smiley.png")),
-            }),
-        ),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(multi_ripple_ring_materials.add(MultiRippleRingMaterial {
-            edge_color: LinearRgba::rgb(1.0, 1.0, 1.0),
-            base_color: LinearRgba::rgb(0.3, 1.0, 0.4),
-        })),
-    ));
-
-    commands.spawn((
-        Mesh3d(meshes.add(Cuboid::from_length(1.0 / 8.0))),
-        MeshMaterial3d(fresnel_materials.add(FresnelMaterial {
-            sharpness: Vec4::splat(2.0),
-        })),
-        Rotate,
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(fire_materials.add(FireMaterial {})),
-    ));
-
-    let cylinder_mesh = Cylinder::new(0.125, 0.25).mesh().without_caps().build();
-    let cylinder_rotation = Quat::from_axis_angle(Vec3::X, PI / 4.0);
-    commands.spawn((
-        Mesh3d(meshes.add(cylinder_mesh)),
-        Transform::from_rotation(cylinder_rotation),
-        MeshMaterial3d(jackpot_materials.add(Jackpot {})),
-    ));
-
-    commands.spawn((
-        Mesh3d(meshes.add(Plane3d::default().mesh().size(0.25, 0.25).subdivisions(20))),
-        Transform::from_rotation(Quat::from_axis_angle(Vec3::X, PI / 4.0)),
-        MeshMaterial3d(ripple_materials.add(RippleMaterial {})),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(vertex_materials.add(VertexTest {})),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(sparks_materials.add(SparksMaterial {})),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(smoke_bomb_materials.add(SmokeBombMaterial {})),
-    ));
-
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(rocks_materials.add(RocksMaterial {})),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(ripple_ring_materials.add(RippleRingMaterial {
-            edge_color: LinearRgba::rgb(1.0, 1.0, 1.0),
-            base_color: LinearRgba::rgb(0.3, 1.0, 0.4),
-            pack: Vec4::new(
-                0.7,  // duration
-                0.05, // ring_thickness
-                0.0, 0.0, // Padding for WASM
-            ),
-        })),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(line_field_materials.add(LineFieldMaterial {
-            edge_color: LinearRgba::rgb(1.0, 1.0, 1.0),
-            base_color: LinearRgba::rgb(0.3, 1.0, 0.4),
-            pack: LFPack {
-                speed: 1.0,
-                angle: 0.0,
-                line_thickness: 0.01,
-                layer_count: 7,
-            },
-        })),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(explosion_materials.add(HitSparkMaterial {
-            edge_color: LinearRgba::rgb(1.0, 0.2, 0.05),
-            mid_color: LinearRgba::rgb(1.0, 1.0, 0.1),
-            base_color: LinearRgba::rgb(1.0, 1.0, 1.0),
-        })),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(block_materials.add(BlockMaterial {
-            edge_color: LinearRgba::rgb(0.1, 0.2, 1.0),
-            base_color: LinearRgba::rgb(1.0, 1.0, 1.0),
-        })),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(clink_materials.add(ClinkMaterial {
-            edge_color: LinearRgba::rgb(0.9, 0.1, 0.9),
-            base_color: LinearRgba::rgb(1.0, 0.5, 1.0),
-        })),
-    ));
-
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(burst_materials.add(BurstMaterial {})),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(edge_slash_materials.add(EdgeSlashMaterial {})),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(corner_slash_materials.add(CornerSlashMaterial {})),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(lightning_materials.add(LightningMaterial {})),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(spinner_materials.add(SpinnerMaterial {})),
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
-        MeshMaterial3d(focal_line_materials.add(FocalLineMaterial {})),
-    ));
-
     commands.spawn((
         Mesh3d(meshes.add(Rectangle::new(0.25, 0.25))),
         MeshMaterial3d(standard_materials.add(StandardMaterial::default())),
@@ -367,7 +121,21 @@ fn setup(
     ));
 
     commands.spawn((
-        Text::new("Select shader with W/A/S/D or the buttons"),
+        Text::new(
+            "Select shader with W/A/S/D or the buttons, Page Up and Page Down turn the page\n\
+             Space pauses, comma and period step a frame, minus and equals change speed\n\
+             T plays the selected effect once, L toggles looping, F locks time to frames\n\
+             P cycles presets and Shift+P saves one\n\
+             M mutates the selected effect, brackets go back and forward through the mutations\n\
+             C switches between the character palettes, G edits the selected effect's gradient\n\
+             Slash searches by name or tag, Enter keeps the results and Escape clears them\n\
+             X pins the selected effect to compare with the next one, V overlays them and Tab flips\n\
+             N puts the selected effect on another mesh\n\
+             Drag to orbit the preview, scroll to zoom, middle drag to pan and R to reset\n\
+             B changes the background, or drop a PNG onto the window\n\
+             F3 switches every effect to a debug view: alpha, UVs, coordinates or NaNs\n\
+             F4 draws the preview's wireframe, then its normals too",
+        ),
         Node {
             // Pad it out a bit
             left: Val::Px(10.0),
@@ -385,7 +153,12 @@ fn setup(
             padding: UiRect::all(Val::Percent(1.0)),
             ..default()
         },
-        children![button(-1, "previous"), button(1, "next")],
+        children![
+            button(-1, "previous"),
+            playback_controls(),
+            mesh_button(),
+            button(1, "next")
+        ],
     ));
 }
 
@@ -398,9 +171,12 @@ const PRESSED_BUTTON: Color = Color::linear_rgb(0.4, 0.5, 0.8);
 struct ButtonDelta(i32);
 
 fn button(delta: i32, text: &'static str) -> impl Bundle + use<> {
+    (styled_button(text), ButtonDelta(delta))
+}
+
+fn styled_button(text: &'static str) -> impl Bundle + use<> {
     (
         Button,
-        ButtonDelta(delta),
         Node {
             padding: UiRect::all(Val::Px(10.0)),
             border: UiRect::all(Val::Px(3.0)),
@@ -423,53 +199,62 @@ fn rotate_meshes(mut mesh_query: Query<&mut Transform, With<Rotate>>, time: Res<
     }
 }
 
-const ROW_SIZE: usize = 8;
+/// Edge of the tile meshes, the grid scales them to fit
 const SQUARE_EDGE: f32 = 0.25;
-const POS0: Vec3 = Vec3::new(-2.0, 1.0, 0.0);
+/// Where the selected tile is shown, enlarged
+const PREVIEW_POS: Vec3 = Vec3::new(1.0, 0.0, 0.0);
+const PREVIEW_SCALE: f32 = 6.0;
 
-#[allow(clippy::type_complexity)]
 fn button_system(
-    interaction_query: Query<
-        (&Interaction, &ButtonDelta, &Children),
-        (Changed<Interaction>, With<Button>),
-    >,
-    mut text_cols: Query<&mut TextColor>,
+    interaction_query: Query<(&Interaction, &ButtonDelta), Changed<Interaction>>,
     mut selection: ResMut<Selected>,
-    meshes: Query<&Transform, (With<Mesh3d>, Without<Blank>)>,
+    tiles: Query<(), (With<Tile>, Without<FilteredOut>)>,
 ) {
-    let selectables = meshes.iter().count();
-
-    for (interaction, delta, children) in &interaction_query {
-        let mut color = text_cols.get_mut(children[0]).unwrap();
+    let selectables = tiles.iter().count();
+    if selectables == 0 {
+        return;
+    }
 
-        match *interaction {
-            Interaction::Pressed => {
-                *color = PRESSED_BUTTON.into();
-                let Selected(index) = *selection;
-                selection.0 = if index == 0 && delta.0 == -1 {
-                    // Wrap
-                    selectables - 1
-                } else {
-                    ((index as i32 + delta.0) % selectables as i32) as usize
-                };
-            }
-            Interaction::Hovered => {
-                *color = HOVERED_BUTTON.into();
-            }
-            Interaction::None => {
-                *color = NORMAL_BUTTON.into();
-            }
+    for (interaction, delta) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let Selected(index) = *selection;
+            selection.0 = if index == 0 && delta.0 == -1 {
+                // Wrap
+                selectables - 1
+            } else {
+                ((index as i32 + delta.0) % selectables as i32) as usize
+            };
         }
     }
 }
+
+#[allow(clippy::type_complexity)]
+fn button_colors(
+    interaction_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<Button>)>,
+    mut text_cols: Query<&mut TextColor>,
+) {
+    for (interaction, children) in &interaction_query {
+        let mut color = text_cols.get_mut(children[0]).unwrap();
+        *color = match *interaction {
+            Interaction::Pressed => PRESSED_BUTTON.into(),
+            Interaction::Hovered => HOVERED_BUTTON.into(),
+            Interaction::None => NORMAL_BUTTON.into(),
+        };
+    }
+}
 fn keyboard_system(
-    mut keyboard_input_events: MessageReader<KeyboardInput>,
+    mut hotkeys: Hotkeys,
     mut selection: ResMut<Selected>,
-    meshes: Query<&Transform, (With<Mesh3d>, Without<Blank>)>,
+    layout: Res<GridLayout>,
+    tiles: Query<(), (With<Tile>, Without<FilteredOut>)>,
 ) {
-    let selectables = meshes.iter().count();
+    let selectables = tiles.iter().count();
+    if selectables == 0 {
+        return;
+    }
+    let row_size = layout.columns;
     let Selected(selected_index) = *selection;
-    for event in keyboard_input_events.read() {
+    for event in hotkeys.read() {
         if event.repeat || event.state == ButtonState::Released {
             continue;
         }
@@ -479,58 +264,30 @@ fn keyboard_system(
             KeyCode::KeyD => selection.0 = 0,
             KeyCode::KeyA if selected_index == 0 => selection.0 = selectables - 1,
             KeyCode::KeyA => selection.0 -= 1,
-            KeyCode::KeyW if selected_index < ROW_SIZE => {
+            KeyCode::KeyW if selected_index < row_size => {
                 // Last item or same column on last row (wrap
-                let rows = selectables / ROW_SIZE;
-                let last_row_first_index = rows * ROW_SIZE;
-                let selected_col = selected_index % ROW_SIZE;
+                let rows = selectables / row_size;
+                let last_row_first_index = rows * row_size;
+                let selected_col = selected_index % row_size;
                 let same_col_last_row = last_row_first_index + selected_col;
                 if same_col_last_row < selectables {
                     selection.0 = same_col_last_row;
                 } else {
-                    selection.0 = same_col_last_row - ROW_SIZE;
+                    selection.0 = same_col_last_row.saturating_sub(row_size);
                 }
             }
-            KeyCode::KeyW => selection.0 -= ROW_SIZE,
-            KeyCode::KeyS if selected_index > (selectables - ROW_SIZE - 1) => {
-                selection.0 = selected_index % ROW_SIZE
+            KeyCode::KeyW => selection.0 -= row_size,
+            KeyCode::KeyS if selected_index + row_size >= selectables => {
+                selection.0 = selected_index % row_size
+            }
+            KeyCode::KeyS => selection.0 += row_size,
+            KeyCode::PageUp => {
+                selection.0 = selected_index.saturating_sub(layout.page_size());
+            }
+            KeyCode::PageDown => {
+                selection.0 = (selected_index + layout.page_size()).min(selectables - 1);
             }
-            KeyCode::KeyS => selection.0 += ROW_SIZE,
             _ => {}
         }
     }
 }
-
-fn update_selection(
-    selection: ResMut<Selected>,
-    mut meshes: Query<&mut Transform, (With<Mesh3d>, Without<Blank>)>,
-    mut blanks: Query<&mut Transform, With<Blank>>,
-) {
-    let Selected(new_selection) = *selection;
-
-    for (index, mut tf) in meshes.iter_mut().enumerate() {
-        let row = (index / ROW_SIZE) as f32;
-        let col = (index % ROW_SIZE) as f32;
-        let pos = POS0 + SQUARE_EDGE * Vec3::new(col, -row, 0.0);
-
-        if index == new_selection {
-            tf.translation = Vec3::new(1.0, 0.0, 0.0);
-            tf.scale = Vec3::splat(6.0);
-
-            let mut blank_tf = blanks.single_mut().unwrap();
-            blank_tf.translation = pos;
-        } else {
-            tf.translation = pos;
-            tf.scale = Vec3::splat(1.0);
-        }
-    }
-}
-
-fn pad_to(input: Vec<Vec3>, desired_len: usize) -> Vec<Vec4> {
-    let padding = std::iter::repeat_n(Vec3::default(), desired_len - input.len());
-    input
-        .into_iter()
-        .chain(padding)
-        .map(|v| v.extend(0.0))
-        .collect::<Vec<_>>()
-}
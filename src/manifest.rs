@@ -0,0 +1,226 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+use bevy_shader_testing::registry::ShaderRegistry;
+use ron::extensions::Extensions;
+use serde::Deserialize;
+
+use crate::{labels::TileInfo, Rotate, Tile, SQUARE_EDGE};
+
+const MANIFEST_PATH: &str = "tiles.gallery.ron";
+
+/// Loads `assets/tiles.gallery.ron` and keeps the grid in sync with it
+pub struct ManifestPlugin;
+
+impl Plugin for ManifestPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<GalleryManifest>()
+            .init_asset_loader::<GalleryManifestLoader>()
+            .init_resource::<ShaderRegistry>()
+            .add_systems(Startup, load_manifest)
+            .add_systems(Update, spawn_tiles);
+    }
+}
+
+#[derive(Asset, TypePath, Debug, Deserialize)]
+pub struct GalleryManifest {
+    pub tiles: Vec<TileSpec>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TileSpec {
+    /// Type name of a registered material, such as `HitSparkMaterial`
+    pub material: String,
+    /// Shown instead of the material's name, for tiles that show the same one
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Shown instead of the material's description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Added to the material's own tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub mesh: MeshSpec,
+    /// Euler angles in degrees. Translation and scale are owned by the grid layout
+    #[serde(default)]
+    pub rotation: Vec3,
+    /// Keep spinning the tile, useful for 3D meshes
+    #[serde(default)]
+    pub rotate: bool,
+    /// Uniform values, missing fields use the material's defaults
+    #[serde(default)]
+    pub params: Option<ron::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum MeshSpec {
+    Rectangle(f32, f32),
+    Cuboid(f32),
+    Cylinder {
+        radius: f32,
+        height: f32,
+        #[serde(default)]
+        caps: bool,
+    },
+    Plane {
+        size: f32,
+        subdivisions: u32,
+    },
+    /// UV sphere, by radius
+    Sphere(f32),
+    Torus {
+        inner_radius: f32,
+        outer_radius: f32,
+    },
+}
+
+impl Default for MeshSpec {
+    fn default() -> Self {
+        MeshSpec::Rectangle(SQUARE_EDGE, SQUARE_EDGE)
+    }
+}
+
+impl From<&MeshSpec> for Mesh {
+    fn from(spec: &MeshSpec) -> Self {
+        match *spec {
+            MeshSpec::Rectangle(width, height) => Rectangle::new(width, height).into(),
+            MeshSpec::Cuboid(length) => Cuboid::from_length(length).into(),
+            MeshSpec::Cylinder {
+                radius,
+                height,
+                caps: true,
+            } => Cylinder::new(radius, height).into(),
+            MeshSpec::Cylinder {
+                radius,
+                height,
+                caps: false,
+            } => Cylinder::new(radius, height).mesh().without_caps().build(),
+            MeshSpec::Plane { size, subdivisions } => Plane3d::default()
+                .mesh()
+                .size(size, size)
+                .subdivisions(subdivisions)
+                .build(),
+            MeshSpec::Sphere(radius) => Sphere::new(radius).mesh().uv(32, 18),
+            MeshSpec::Torus {
+                inner_radius,
+                outer_radius,
+            } => Torus::new(inner_radius, outer_radius).into(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct GalleryManifestLoader;
+
+impl AssetLoader for GalleryManifestLoader {
+    type Asset = GalleryManifest;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<GalleryManifest, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        // Saves writing Some(...) around every params block
+        let options = ron::Options::default().with_default_extension(Extensions::IMPLICIT_SOME);
+        Ok(options.from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["gallery.ron"]
+    }
+}
+
+#[derive(Debug, Resource)]
+struct Manifest(Handle<GalleryManifest>);
+
+/// Marks tiles spawned from the manifest
+#[derive(Debug, Component)]
+struct ManifestTile;
+
+fn load_manifest(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Manifest(asset_server.load(MANIFEST_PATH)));
+}
+
+/// Respawns every tile when the manifest is loaded or hot-reloaded
+fn spawn_tiles(
+    mut commands: Commands,
+    mut events: MessageReader<AssetEvent<GalleryManifest>>,
+    manifest: Res<Manifest>,
+    manifests: Res<Assets<GalleryManifest>>,
+    tiles: Query<Entity, With<ManifestTile>>,
+) {
+    let changed = events
+        .read()
+        .filter(|event| {
+            event.is_loaded_with_dependencies(&manifest.0) || event.is_modified(&manifest.0)
+        })
+        .count()
+        > 0;
+    if !changed {
+        return;
+    }
+    let Some(manifest) = manifests.get(&manifest.0) else {
+        return;
+    };
+
+    for tile in &tiles {
+        commands.entity(tile).despawn();
+    }
+
+    for (index, spec) in manifest.tiles.iter().cloned().enumerate() {
+        commands.queue(move |world: &mut World| spawn_tile(world, index, spec));
+    }
+}
+
+fn spawn_tile(world: &mut World, index: usize, spec: TileSpec) {
+    let Some(entry) = world.resource::<ShaderRegistry>().get(&spec.material) else {
+        warn!(
+            "Gallery manifest refers to unknown material {}",
+            spec.material
+        );
+        return;
+    };
+
+    let mesh = world.resource_mut::<Assets<Mesh>>().add(&spec.mesh);
+    let rotation = Quat::from_euler(
+        EulerRot::XYZ,
+        spec.rotation.x.to_radians(),
+        spec.rotation.y.to_radians(),
+        spec.rotation.z.to_radians(),
+    );
+    let info = TileInfo {
+        name: spec.name.unwrap_or_else(|| entry.name.to_string()),
+        description: spec
+            .description
+            .unwrap_or_else(|| entry.description.to_string()),
+        tags: entry
+            .tags
+            .iter()
+            .map(|tag| tag.to_string())
+            .chain(spec.tags)
+            .collect(),
+        shader: entry.fragment_shader.as_ref().map(TileInfo::source_file),
+    };
+    let mut tile = world.spawn((
+        Tile(index),
+        ManifestTile,
+        info,
+        Mesh3d(mesh),
+        Transform::from_rotation(rotation),
+    ));
+    if spec.rotate {
+        tile.insert(Rotate);
+    }
+
+    if let Err(err) = (entry.insert_material)(&mut tile, spec.params) {
+        warn!("Invalid params for {} tile: {err}", entry.name);
+        tile.despawn();
+    }
+}
@@ -1,74 +1,136 @@
+use std::f32::consts::PI;
+
 use bevy::{
     prelude::*,
-    reflect::TypePath,
     render::render_resource::{AsBindGroup, ShaderType},
-    shader::ShaderRef,
+};
+use serde::{Deserialize, Deserializer};
+use shader_material_derive::{ParamRanges, ShaderMaterial};
+
+use crate::{
+    clock::EffectClock,
+    debug::DebugView,
+    gradient::{BURST_GRADIENT, JACKPOT_GRADIENT, LIGHTNING_GRADIENT, MANDELBROT_GRADIENT},
+    ranges::ReflectParamRanges,
+    registry::ShaderEntry,
 };
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/fresnel.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
 pub struct FresnelMaterial {
     #[uniform(0)]
     pub sharpness: Vec4, // Needed for WASM padding, only X is used
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
 }
 
-impl Material for FresnelMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/fresnel.wgsl".into()
+impl Default for FresnelMaterial {
+    fn default() -> Self {
+        Self {
+            sharpness: Vec4::splat(2.0),
+            clock: EffectClock::default(),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+impl ShaderEntry for FresnelMaterial {
+    const NAME: &'static str = "Fresnel";
+    const DESCRIPTION: &'static str = "Glowing rim that follows the edges of a 3D mesh";
+    const TAGS: &'static [&'static str] = &["3d", "rim"];
 }
 
-#[derive(Debug, Clone, ShaderType)]
+#[derive(Debug, Clone, Reflect, ShaderType, Deserialize, ParamRanges)]
+#[reflect(ParamRanges)]
 #[repr(C, align(16))]
 pub struct LFPack {
+    #[range(0.2, 3.0)]
     pub speed: f32,
+    #[range(0.0, PI)]
     pub angle: f32,
+    #[range(0.0, 0.2)]
     pub line_thickness: f32,
+    #[range(1.0, 12.0)]
     pub layer_count: i32,
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ParamRanges, ShaderMaterial)]
+#[reflect(ParamRanges)]
+#[shader("embedded://bevy_shader_testing/shaders/line_field.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
 pub struct LineFieldMaterial {
     #[uniform(0)]
+    #[range(0.0, 1.0)]
     pub base_color: LinearRgba,
     #[uniform(1)]
+    #[range(0.0, 1.0)]
     pub edge_color: LinearRgba,
     #[uniform(2)]
     pub pack: LFPack,
+    #[uniform(3)]
+    #[clock]
+    pub clock: EffectClock,
 }
 
-impl Material for LineFieldMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/line_field.wgsl".into()
+impl Default for LineFieldMaterial {
+    fn default() -> Self {
+        Self {
+            edge_color: LinearRgba::rgb(1.0, 1.0, 1.0),
+            base_color: LinearRgba::rgb(0.3, 1.0, 0.4),
+            pack: LFPack {
+                speed: 1.0,
+                angle: 0.0,
+                line_thickness: 0.01,
+                layer_count: 7,
+            },
+            clock: EffectClock::default(),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+impl ShaderEntry for LineFieldMaterial {
+    const NAME: &'static str = "Line field";
+    const DESCRIPTION: &'static str = "Layers of streaks sliding past, for dashes and speed";
+    const TAGS: &'static [&'static str] = &["lines", "background"];
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/multi_ripple_ring.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
 pub struct MultiRippleRingMaterial {
     #[uniform(0)]
     pub base_color: LinearRgba,
     #[uniform(1)]
     pub edge_color: LinearRgba,
+    #[uniform(2)]
+    #[clock]
+    pub clock: EffectClock,
 }
 
-impl Material for MultiRippleRingMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/multi_ripple_ring.wgsl".into()
+impl Default for MultiRippleRingMaterial {
+    fn default() -> Self {
+        Self {
+            edge_color: LinearRgba::rgb(1.0, 1.0, 1.0),
+            base_color: LinearRgba::rgb(0.3, 1.0, 0.4),
+            clock: EffectClock::with_cycle(3.0),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+impl ShaderEntry for MultiRippleRingMaterial {
+    const NAME: &'static str = "Multi ripple ring";
+    const DESCRIPTION: &'static str = "Several rings spreading out from a hit";
+    const TAGS: &'static [&'static str] = &["hit", "ring"];
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/ripple_ring.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
 pub struct RippleRingMaterial {
     #[uniform(0)]
     pub base_color: LinearRgba,
@@ -76,21 +138,36 @@ pub struct RippleRingMaterial {
     pub edge_color: LinearRgba,
     #[uniform(2)]
     pub pack: Vec4,
-    // duration = pack.x;
-    // ring_thickness = pack.y;
+    // ring_thickness = pack.x;
+    #[uniform(3)]
+    #[clock]
+    pub clock: EffectClock,
 }
 
-impl Material for RippleRingMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/ripple_ring.wgsl".into()
+impl Default for RippleRingMaterial {
+    fn default() -> Self {
+        Self {
+            edge_color: LinearRgba::rgb(1.0, 1.0, 1.0),
+            base_color: LinearRgba::rgb(0.3, 1.0, 0.4),
+            pack: Vec4::new(
+                0.05, // ring_thickness
+                0.0, 0.0, 0.0, // Padding for WASM
+            ),
+            clock: EffectClock::with_cycle(0.7),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+impl ShaderEntry for RippleRingMaterial {
+    const NAME: &'static str = "Ripple ring";
+    const DESCRIPTION: &'static str = "A single ring spreading out from a hit";
+    const TAGS: &'static [&'static str] = &["hit", "ring"];
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/hitspark.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
 pub struct HitSparkMaterial {
     #[uniform(0)]
     pub base_color: LinearRgba,
@@ -98,358 +175,1149 @@ pub struct HitSparkMaterial {
     pub mid_color: LinearRgba,
     #[uniform(2)]
     pub edge_color: LinearRgba,
+    #[uniform(3)]
+    #[clock]
+    pub clock: EffectClock,
 }
 
-impl Material for HitSparkMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/hitspark.wgsl".into()
+impl Default for HitSparkMaterial {
+    fn default() -> Self {
+        Self {
+            edge_color: LinearRgba::rgb(1.0, 0.2, 0.05),
+            mid_color: LinearRgba::rgb(1.0, 1.0, 0.1),
+            base_color: LinearRgba::rgb(1.0, 1.0, 1.0),
+            clock: EffectClock::default(),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+impl ShaderEntry for HitSparkMaterial {
+    const NAME: &'static str = "Hit spark";
+    const DESCRIPTION: &'static str = "Star shaped flash where a hit lands";
+    const TAGS: &'static [&'static str] = &["hit"];
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/blocking.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
 pub struct BlockMaterial {
     #[uniform(0)]
     pub base_color: LinearRgba,
     #[uniform(1)]
     pub edge_color: LinearRgba,
+    #[uniform(2)]
+    #[clock]
+    pub clock: EffectClock,
 }
 
-impl Material for BlockMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/blocking.wgsl".into()
+impl Default for BlockMaterial {
+    fn default() -> Self {
+        Self {
+            edge_color: LinearRgba::rgb(0.1, 0.2, 1.0),
+            base_color: LinearRgba::rgb(1.0, 1.0, 1.0),
+            clock: EffectClock::default(),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+impl ShaderEntry for BlockMaterial {
+    const NAME: &'static str = "Block";
+    const DESCRIPTION: &'static str = "Flash for a hit that was blocked";
+    const TAGS: &'static [&'static str] = &["hit"];
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/clink.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
 pub struct ClinkMaterial {
     #[uniform(0)]
     pub base_color: LinearRgba,
     #[uniform(1)]
     pub edge_color: LinearRgba,
+    #[uniform(2)]
+    #[clock]
+    pub clock: EffectClock,
 }
 
-impl Material for ClinkMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/clink.wgsl".into()
+impl Default for ClinkMaterial {
+    fn default() -> Self {
+        Self {
+            edge_color: LinearRgba::rgb(0.9, 0.1, 0.9),
+            base_color: LinearRgba::rgb(1.0, 0.5, 1.0),
+            clock: EffectClock::default(),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+impl ShaderEntry for ClinkMaterial {
+    const NAME: &'static str = "Clink";
+    const DESCRIPTION: &'static str = "Small flash for two attacks clashing";
+    const TAGS: &'static [&'static str] = &["hit"];
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct SpinnerMaterial {}
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Default, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/spinner.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct SpinnerMaterial {
+    #[uniform(0)]
+    #[clock]
+    pub clock: EffectClock,
+}
 
-impl Material for SpinnerMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/spinner.wgsl".into()
-    }
+impl ShaderEntry for SpinnerMaterial {
+    const NAME: &'static str = "Spinner";
+    const DESCRIPTION: &'static str = "Rotating loading indicator";
+    const TAGS: &'static [&'static str] = &["ui"];
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Default, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/focal_lines.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct FocalLineMaterial {
+    #[uniform(0)]
+    #[clock]
+    pub clock: EffectClock,
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct FocalLineMaterial {}
+impl ShaderEntry for FocalLineMaterial {
+    const NAME: &'static str = "Focal lines";
+    const DESCRIPTION: &'static str = "Speed lines closing in on the center";
+    const TAGS: &'static [&'static str] = &["lines", "background"];
+}
 
-impl Material for FocalLineMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/focal_lines.wgsl".into()
-    }
+#[derive(Debug, Clone, Reflect, ShaderType, Deserialize, ParamRanges)]
+#[reflect(ParamRanges)]
+#[serde(default)]
+pub struct EdgeSlashParams {
+    #[range(0.5, 4.0)]
+    pub edge_sharpness: f32,
+    #[range(0.0, 1.0)]
+    pub color: LinearRgba,
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+impl Default for EdgeSlashParams {
+    fn default() -> Self {
+        Self {
+            edge_sharpness: 2.0,
+            color: LinearRgba::rgb(0.0, 1.0, 0.5),
+        }
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct EdgeSlashMaterial {}
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/edge_slash.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct EdgeSlashMaterial {
+    #[uniform(0)]
+    pub params: EdgeSlashParams,
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
+}
 
-impl Material for EdgeSlashMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/edge_slash.wgsl".into()
+impl Default for EdgeSlashMaterial {
+    fn default() -> Self {
+        Self {
+            params: default(),
+            clock: EffectClock::with_cycle(2.0),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+impl ShaderEntry for EdgeSlashMaterial {
+    const NAME: &'static str = "Edge slash";
+    const DESCRIPTION: &'static str = "A slash sweeping in from the edge";
+    const TAGS: &'static [&'static str] = &["slash"];
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct BurstMaterial {}
+#[derive(Debug, Clone, Reflect, ShaderType, Deserialize, ParamRanges)]
+#[reflect(ParamRanges)]
+#[serde(default)]
+pub struct BurstParams {
+    #[range(0.02, 0.3)]
+    pub main_thickness: f32,
+    #[range(0.0, 0.1)]
+    pub secondary_start: Vec2,
+    #[range(0.3, 1.0)]
+    pub secondary_end: Vec2,
+    #[range(0.0, 0.4)]
+    pub secondary_movement: Vec2,
+    #[range(0.02, 0.3)]
+    pub secondary_thickness: f32,
+    #[range(0.0, 0.1)]
+    pub edge: f32,
+    // Padding for WASM, uniforms need to be a multiple of 16 bytes
+    #[serde(skip)]
+    #[reflect(skip_serializing)]
+    _padding: Vec2,
+}
 
-impl Material for BurstMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/burst.wgsl".into()
+impl Default for BurstParams {
+    fn default() -> Self {
+        Self {
+            main_thickness: 0.1,
+            secondary_start: Vec2::new(0.01, 0.015),
+            secondary_end: Vec2::new(0.6, 0.9),
+            secondary_movement: Vec2::new(0.1, 0.2),
+            secondary_thickness: 0.1,
+            edge: 0.03,
+            _padding: Vec2::ZERO,
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/burst.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct BurstMaterial {
+    #[uniform(0)]
+    pub params: BurstParams,
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
+    /// Colors from the middle of the streaks at the left to their edges at the right
+    #[texture(2)]
+    #[sampler(3)]
+    #[serde(skip)]
+    #[reflect(ignore, clone)]
+    pub gradient: Option<Handle<Image>>,
+}
+
+impl Default for BurstMaterial {
+    fn default() -> Self {
+        Self {
+            params: default(),
+            clock: EffectClock::with_cycle(2.0),
+            gradient: Some(BURST_GRADIENT),
+        }
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct CornerSlashMaterial {}
+impl ShaderEntry for BurstMaterial {
+    const NAME: &'static str = "Burst";
+    const DESCRIPTION: &'static str = "Rays bursting out from the center";
+    const TAGS: &'static [&'static str] = &["hit", "gradient"];
+}
 
-impl Material for CornerSlashMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/corner_slash.wgsl".into()
-    }
+#[derive(Debug, Clone, Reflect, ShaderType, Deserialize, ParamRanges)]
+#[reflect(ParamRanges)]
+#[serde(default)]
+pub struct CornerSlashParams {
+    #[range(0.5, 4.0)]
+    pub angle_sharpness: f32,
+    #[range(0.0, 1.0)]
+    pub color: LinearRgba,
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+impl Default for CornerSlashParams {
+    fn default() -> Self {
+        Self {
+            angle_sharpness: 1.5,
+            color: LinearRgba::rgb(1.0, 0.0, 1.0),
+        }
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct LightningMaterial {}
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/corner_slash.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct CornerSlashMaterial {
+    #[uniform(0)]
+    pub params: CornerSlashParams,
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
+}
 
-impl Material for LightningMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/lightning.wgsl".into()
+impl Default for CornerSlashMaterial {
+    fn default() -> Self {
+        Self {
+            params: default(),
+            clock: EffectClock::with_cycle(2.0),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+impl ShaderEntry for CornerSlashMaterial {
+    const NAME: &'static str = "Corner slash";
+    const DESCRIPTION: &'static str = "A slash cutting in from a corner";
+    const TAGS: &'static [&'static str] = &["slash"];
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct RocksMaterial {}
+#[derive(Debug, Clone, Reflect, ShaderType, Deserialize, ParamRanges)]
+#[reflect(ParamRanges)]
+#[serde(default)]
+pub struct LightningParams {
+    #[range(1.0, 8.0)]
+    pub zigzags: f32,
+    #[range(0.2, 1.0)]
+    pub sharpness: f32,
+    #[range(0.0, 0.3)]
+    pub max_dip: f32,
+    #[range(0.0, 0.3)]
+    pub max_bump: f32,
+    #[range(0.02, 0.3)]
+    pub thickness: f32,
+    #[range(0.0, 0.5)]
+    pub smoothing: f32,
+    #[range(0.5, 6.0)]
+    pub wave_thickness: f32,
+    #[range(5.0, 30.0)]
+    pub wave_speed: f32,
+}
 
-impl Material for RocksMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/rocks.wgsl".into()
+impl Default for LightningParams {
+    fn default() -> Self {
+        Self {
+            zigzags: 3.0,
+            sharpness: 0.8,
+            max_dip: 0.1,
+            max_bump: 0.075,
+            thickness: 0.1,
+            smoothing: 0.15,
+            wave_thickness: 3.0,
+            wave_speed: 18.0,
+        }
     }
+}
+
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/lightning.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct LightningMaterial {
+    #[uniform(0)]
+    pub params: LightningParams,
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
+    /// Colors from the core of the bolt at the left to its edge at the right
+    #[texture(2)]
+    #[sampler(3)]
+    #[serde(skip)]
+    #[reflect(ignore, clone)]
+    pub gradient: Option<Handle<Image>>,
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+impl Default for LightningMaterial {
+    fn default() -> Self {
+        Self {
+            params: default(),
+            clock: EffectClock::with_cycle(2.0),
+            gradient: Some(LIGHTNING_GRADIENT),
+        }
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct Jackpot {}
+impl ShaderEntry for LightningMaterial {
+    const NAME: &'static str = "Lightning";
+    const DESCRIPTION: &'static str = "Branching bolts crackling across";
+    const TAGS: &'static [&'static str] = &["electric", "gradient"];
+}
 
-impl Material for Jackpot {
-    fn vertex_shader() -> ShaderRef {
-        "shaders/jackpot.wgsl".into()
-    }
+#[derive(Debug, Clone, Reflect, ShaderType, Deserialize, ParamRanges)]
+#[reflect(ParamRanges)]
+#[serde(default)]
+pub struct RocksParams {
+    #[range(2.0, 15.0)]
+    pub gravity: f32,
+    #[range(0.0, 1.0)]
+    pub friction: f32,
+    #[range(0.0, 1.0)]
+    pub land_speed_loss: f32,
+    #[range(0.0, 1.0)]
+    pub elasticity: f32,
+    #[range(0.0, PI / 4.0)]
+    pub min_angle: f32,
+    #[range(PI / 4.0, PI / 2.0)]
+    pub max_angle: f32,
+    #[range(0.5, 2.0)]
+    pub min_velocity: f32,
+    #[range(2.0, 4.0)]
+    pub max_velocity: f32,
+    #[range(0.0, 1.0)]
+    pub size_vel_influence: f32,
+    #[range(0.003, 0.015)]
+    pub min_size: f32,
+    #[range(0.015, 0.04)]
+    pub max_size: f32,
+    pub min_start: f32,
+    #[range(0.0, 0.3)]
+    pub max_start: f32,
+    #[range(0.0, 1.0)]
+    pub border: f32,
+    pub border_color: LinearRgba,
+    pub inner_color: LinearRgba,
+    #[range(0.0, 1000.0)]
+    pub seed: i32,
+    #[range(10.0, 60.0)]
+    pub amount: i32,
+}
 
-    fn fragment_shader() -> ShaderRef {
-        "shaders/jackpot.wgsl".into()
+impl Default for RocksParams {
+    fn default() -> Self {
+        Self {
+            gravity: 9.0,
+            friction: 0.8,
+            land_speed_loss: 0.5,
+            elasticity: 0.4,
+            min_angle: PI / 8.0,
+            max_angle: PI * 0.35,
+            min_velocity: 1.0,
+            max_velocity: 2.5,
+            size_vel_influence: 0.5,
+            min_size: 0.007,
+            max_size: 0.025,
+            min_start: 0.0,
+            max_start: 0.1,
+            border: 0.5,
+            border_color: LinearRgba::new(0.13, 0.13, 0.13, 0.13),
+            inner_color: LinearRgba::new(0.33, 0.33, 0.33, 0.33),
+            seed: 420,
+            amount: 30,
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/rocks.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct RocksMaterial {
+    #[uniform(0)]
+    pub params: RocksParams,
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
+}
 
-    fn specialize(
-        _pipeline: &bevy::pbr::MaterialPipeline,
-        descriptor: &mut bevy::render::render_resource::RenderPipelineDescriptor,
-        _layout: &bevy::mesh::MeshVertexBufferLayoutRef,
-        _key: bevy::pbr::MaterialPipelineKey<Self>,
-    ) -> Result<(), bevy::render::render_resource::SpecializedMeshPipelineError> {
-        descriptor.primitive.cull_mode = None;
-        Ok(())
+impl Default for RocksMaterial {
+    fn default() -> Self {
+        Self {
+            params: default(),
+            clock: EffectClock::with_cycle(3.0),
+        }
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct RippleMaterial {}
+impl ShaderEntry for RocksMaterial {
+    const NAME: &'static str = "Rocks";
+    const DESCRIPTION: &'static str = "Chunks of rock thrown up from the ground";
+    const TAGS: &'static [&'static str] = &["particles"];
+}
 
-impl Material for RippleMaterial {
-    fn vertex_shader() -> ShaderRef {
-        "shaders/ripple.wgsl".into()
-    }
+#[derive(Debug, Clone, Reflect, ShaderType, Deserialize, ParamRanges)]
+#[reflect(ParamRanges)]
+#[serde(default)]
+pub struct JackpotParams {
+    /// Percentage, timing
+    #[range(0.5, 0.9)]
+    pub peak: f32,
+    /// Percentage, how close to peak change to mid-high colors
+    #[range(0.0, 0.3)]
+    pub color_border: f32,
+    #[range(0.1, 0.4)]
+    pub max_height: f32,
+    /// Relative
+    #[range(0.05, 0.3)]
+    pub width_at_peak: f32,
+    #[range(2.0, 10.0)]
+    pub steepness: f32,
+    pub line_color: LinearRgba,
+    pub line_outline_color: LinearRgba,
+    #[range(0.5, 4.0)]
+    pub color_fade: f32,
+    /// Without outline
+    #[range(0.005, 0.03)]
+    pub line_inner_thickness: f32,
+    /// Including outline
+    #[range(0.03, 0.1)]
+    pub line_outer_thickness: f32,
+}
 
-    fn fragment_shader() -> ShaderRef {
-        "shaders/ripple.wgsl".into()
+impl Default for JackpotParams {
+    fn default() -> Self {
+        Self {
+            peak: 0.75,
+            color_border: 0.1,
+            max_height: 0.2,
+            width_at_peak: 0.1,
+            steepness: 6.0,
+            line_color: LinearRgba::rgb(1.0, 1.0, 1.0),
+            line_outline_color: LinearRgba::rgb(0.0, 0.0, 0.0),
+            color_fade: 2.0,
+            line_inner_thickness: 0.01,
+            line_outer_thickness: 0.05,
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/jackpot.wgsl", vertex, cull = none, alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct Jackpot {
+    #[uniform(0)]
+    pub params: JackpotParams,
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
+    /// Colors from far before the peak at the left to the peak itself at the right
+    #[texture(2)]
+    #[sampler(3)]
+    #[serde(skip)]
+    #[reflect(ignore, clone)]
+    pub gradient: Option<Handle<Image>>,
 }
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct FireMaterial {}
 
-impl Material for FireMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/fire.wgsl".into()
+impl Default for Jackpot {
+    fn default() -> Self {
+        Self {
+            params: default(),
+            clock: EffectClock::with_cycle(2.0),
+            gradient: Some(JACKPOT_GRADIENT),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+impl ShaderEntry for Jackpot {
+    const NAME: &'static str = "Jackpot";
+    const DESCRIPTION: &'static str = "Glowing wave running around a cylinder";
+    const TAGS: &'static [&'static str] = &["3d", "vertex", "gradient"];
 }
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct SmokeBombMaterial {}
 
-impl Material for SmokeBombMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/smoke_bomb.wgsl".into()
-    }
+#[derive(Debug, Clone, Reflect, ShaderType, Deserialize, ParamRanges)]
+#[reflect(ParamRanges)]
+#[serde(default)]
+pub struct RippleParams {
+    #[range(0.0, 1.0)]
+    pub edge_color: LinearRgba,
+    #[range(0.0, 1.0)]
+    pub base_color: LinearRgba,
+    #[range(0.0, 0.5)]
+    pub max_height: f32,
+    #[range(0.1, 1.0)]
+    pub ring_thickness: f32,
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+impl Default for RippleParams {
+    fn default() -> Self {
+        Self {
+            edge_color: LinearRgba::new(1.0, 0.0, 0.0, 1.0),
+            base_color: LinearRgba::new(0.0, 1.0, 0.0, 1.0),
+            max_height: 0.2,
+            ring_thickness: 0.6,
+        }
     }
 }
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct SparksMaterial {}
 
-impl Material for SparksMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/sparks.wgsl".into()
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/ripple.wgsl", vertex, alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct RippleMaterial {
+    #[uniform(0)]
+    pub params: RippleParams,
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
+}
+
+impl Default for RippleMaterial {
+    fn default() -> Self {
+        Self {
+            params: default(),
+            clock: EffectClock::with_cycle(2.0),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+impl RippleMaterial {
+    /// How far the vertex stage of `ripple.wgsl` pushes a vertex along its
+    /// normal, for drawing the displaced plane on the CPU
+    ///
+    /// The shader adds the offset to the clip space position, after projecting,
+    /// so this is in clip space units too.
+    pub fn height(&self, uv: Vec2) -> f32 {
+        let centered = 2.0 * (uv - 0.5);
+        let time = self.clock.cycle_progress();
+
+        let half_thickness = self.params.ring_thickness / 2.0;
+        let ease_out_quint = 1.0 - (1.0 - time).powi(5);
+        let norm_t = ease_out_quint * (1.0 - half_thickness);
+        let target_dist = (centered.length() - norm_t).abs();
+        let dist_fade = 1.0 - (PI / 2.0 * target_dist / half_thickness).powi(2);
+
+        let ease_in_circ = 1.0 - (1.0 - time.powi(2)).sqrt();
+        dist_fade * self.params.max_height - ease_in_circ
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct VertexTest {}
+impl ShaderEntry for RippleMaterial {
+    const NAME: &'static str = "Ripple";
+    const DESCRIPTION: &'static str = "Wave rippling out across a subdivided plane";
+    const TAGS: &'static [&'static str] = &["3d", "vertex", "ring"];
+}
+
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Default, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/fire.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct FireMaterial {
+    #[uniform(0)]
+    #[clock]
+    pub clock: EffectClock,
+}
+
+impl ShaderEntry for FireMaterial {
+    const NAME: &'static str = "Fire";
+    const DESCRIPTION: &'static str = "Flickering noise based flames";
+    const TAGS: &'static [&'static str] = &["fire", "background"];
+}
 
-impl Material for VertexTest {
-    fn vertex_shader() -> ShaderRef {
-        "shaders/vertex.wgsl".into()
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/smoke_bomb.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct SmokeBombMaterial {
+    #[uniform(0)]
+    #[clock]
+    pub clock: EffectClock,
+}
+
+impl Default for SmokeBombMaterial {
+    fn default() -> Self {
+        Self {
+            clock: EffectClock::with_cycle(3.0),
+        }
     }
+}
 
-    fn fragment_shader() -> ShaderRef {
-        "shaders/vertex.wgsl".into()
+impl ShaderEntry for SmokeBombMaterial {
+    const NAME: &'static str = "Smoke bomb";
+    const DESCRIPTION: &'static str = "Puffs of smoke billowing out";
+    const TAGS: &'static [&'static str] = &["particles", "smoke"];
+}
+
+#[derive(Debug, Clone, Reflect, ShaderType, Deserialize, ParamRanges)]
+#[reflect(ParamRanges)]
+#[serde(default)]
+pub struct SparksParams {
+    #[range(2.0, 15.0)]
+    pub gravity: f32,
+    #[range(0.0, 1.0)]
+    pub land_speed_loss: f32,
+    #[range(0.0, 1.0)]
+    pub elasticity: f32,
+    #[range(0.0, PI / 4.0)]
+    pub min_angle: f32,
+    #[range(PI / 4.0, PI / 2.0)]
+    pub max_angle: f32,
+    #[range(1.0, 3.0)]
+    pub min_velocity: f32,
+    #[range(3.0, 7.0)]
+    pub max_velocity: f32,
+    #[range(0.0, 1.0)]
+    pub size_vel_influence: f32,
+    #[range(0.0, 0.5)]
+    pub deceleration_x: f32,
+    #[range(0.01, 0.03)]
+    pub min_size: f32,
+    #[range(0.03, 0.06)]
+    pub max_size: f32,
+    #[range(0.0, 0.2)]
+    pub shrink_speed: f32,
+    pub min_start: f32,
+    #[range(0.0, 0.4)]
+    pub max_start: f32,
+    #[range(0.0, 0.5)]
+    pub border: f32,
+    #[range(0.5, 1.0)]
+    pub border_color: LinearRgba,
+    #[range(0.5, 1.0)]
+    pub inner_color: LinearRgba,
+    #[range(0.0, 1000.0)]
+    pub seed: i32,
+    #[range(10.0, 80.0)]
+    pub amount: i32,
+}
+
+impl Default for SparksParams {
+    fn default() -> Self {
+        Self {
+            gravity: 8.0,
+            land_speed_loss: 0.5,
+            elasticity: 0.4,
+            min_angle: PI / 6.0,
+            max_angle: PI * 0.3,
+            min_velocity: 1.8,
+            max_velocity: 5.0,
+            size_vel_influence: 0.5,
+            deceleration_x: 0.2,
+            min_size: 0.02,
+            max_size: 0.04,
+            shrink_speed: 0.08,
+            min_start: 0.0,
+            max_start: 0.2,
+            border: 0.2,
+            border_color: LinearRgba::new(1.0, 0.7, 0.3, 1.0),
+            inner_color: LinearRgba::new(1.0, 0.8, 0.6, 1.0),
+            seed: 420,
+            amount: 40,
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/sparks.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct SparksMaterial {
+    #[uniform(0)]
+    pub params: SparksParams,
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
+}
+
+impl Default for SparksMaterial {
+    fn default() -> Self {
+        Self {
+            params: default(),
+            clock: EffectClock::with_cycle(3.0),
+        }
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+impl ShaderEntry for SparksMaterial {
+    const NAME: &'static str = "Sparks";
+    const DESCRIPTION: &'static str = "Sparks bouncing off the ground";
+    const TAGS: &'static [&'static str] = &["particles", "hit"];
+}
+
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Default, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/vertex.wgsl", vertex, alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct VertexTest {
+    #[uniform(0)]
+    #[clock]
+    pub clock: EffectClock,
+}
+
+impl ShaderEntry for VertexTest {
+    const NAME: &'static str = "Vertex test";
+    const DESCRIPTION: &'static str = "Squashes the mesh from the vertex stage";
+    const TAGS: &'static [&'static str] = &["vertex", "test"];
+}
+
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/bezier.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
 pub struct BezierMaterial {
     #[uniform(0)]
+    #[serde(deserialize_with = "control_points")]
     pub control_points: [Vec4; 16],
     #[uniform(1)]
     pub curves: UVec4, // Padding for WASM, only X matters
     #[texture(2)]
     #[sampler(3)]
+    #[serde(skip)]
+    #[reflect(ignore, clone)]
     pub texture: Option<Handle<Image>>,
+    #[uniform(4)]
+    #[clock]
+    pub clock: EffectClock,
 }
 
-impl Material for BezierMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/bezier.wgsl".into()
+impl Default for BezierMaterial {
+    fn default() -> Self {
+        // Even though we don't use the fourth dimension, Bevy wants them as 4d
+        let control_points = vec![
+            // Set 1
+            Vec3::new(-0.9, -0.9, 2.0),
+            Vec3::new(1.0, 1.0, 10.0),
+            Vec3::new(-2.0, 1.5, 10.0),
+            Vec3::new(-0.2, -0.2, 5.0),
+            // Set 2 (first anchor is assumed to be the last point of previous curve)
+            // Think of it as auto-continuity
+            Vec3::new(1.6, -1.9, 10.0),
+            Vec3::new(-3.0, 0.2, 10.0),
+            Vec3::new(0.9, 0.0, 2.0),
+        ];
+        Self {
+            control_points: pad_to(control_points, 16).as_slice().try_into().unwrap(),
+            curves: UVec4::splat(2),
+            texture: None,
+            clock: EffectClock::with_cycle(3.0),
+        }
     }
+}
+
+impl ShaderEntry for BezierMaterial {
+    const NAME: &'static str = "Bezier";
+    const DESCRIPTION: &'static str = "Strokes along chained bezier curves";
+    const TAGS: &'static [&'static str] = &["curve"];
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+    fn load_assets(&mut self, asset_server: &AssetServer) {
+        self.texture = Some(asset_server.load("pictures/smiley.png"));
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/bezier-swoosh.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
 pub struct BezierSwooshMaterial {
     #[uniform(0)]
+    #[serde(deserialize_with = "control_points")]
     pub control_points: [Vec4; 16],
     #[uniform(1)]
     pub curves: UVec4, // Padding for WASM, only X matters
+    #[uniform(2)]
+    #[clock]
+    pub clock: EffectClock,
 }
 
-impl Material for BezierSwooshMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/bezier-swoosh.wgsl".into()
+impl Default for BezierSwooshMaterial {
+    fn default() -> Self {
+        // Even though we don't use the fourth dimension, Bevy wants them as 4d
+        let control_points = vec![
+            Vec3::new(-0.7, 0.9, 2.0),
+            Vec3::new(1.2, 1.0, 8.0),
+            Vec3::new(-0.2, -0.9, 6.0),
+            Vec3::new(-0.9, 0.7, 0.0),
+        ];
+        Self {
+            control_points: pad_to(control_points, 16).as_slice().try_into().unwrap(),
+            curves: UVec4::splat(1),
+            clock: EffectClock::default(),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+impl ShaderEntry for BezierSwooshMaterial {
+    const NAME: &'static str = "Bezier swoosh";
+    const DESCRIPTION: &'static str = "A swoosh trailing along a bezier curve";
+    const TAGS: &'static [&'static str] = &["curve", "slash"];
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct NormalCubeMaterial {}
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/normal-cube.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct NormalCubeMaterial {
+    #[uniform(0)]
+    #[clock]
+    pub clock: EffectClock,
+}
 
-impl Material for NormalCubeMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/normal-cube.wgsl".into()
+impl Default for NormalCubeMaterial {
+    fn default() -> Self {
+        Self {
+            clock: EffectClock::with_cycle(3.0),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
+impl ShaderEntry for NormalCubeMaterial {
+    const NAME: &'static str = "Normal cube";
+    const DESCRIPTION: &'static str = "Cube drawn flat, lit by a circling light";
+    const TAGS: &'static [&'static str] = &["3d", "test"];
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct SugarCoatMaterial {}
+#[derive(Debug, Clone, Reflect, ShaderType, Deserialize, ParamRanges)]
+#[reflect(ParamRanges)]
+#[serde(default)]
+pub struct SugarCoatParams {
+    #[range(10.0, 100.0)]
+    pub particle_amount: i32,
+    #[range(0.0, 1000.0)]
+    pub particle_seed: i32,
+    #[range(0.5, 5.0)]
+    pub particle_gravity: f32,
+    #[range(-PI / 3.0, 0.0)]
+    pub particle_min_angle: f32,
+    #[range(0.0, PI / 3.0)]
+    pub particle_max_angle: f32,
+    #[range(0.2, 1.0)]
+    pub particle_min_velocity: f32,
+    #[range(1.0, 3.0)]
+    pub particle_max_velocity: f32,
+    #[range(0.0, 1.0)]
+    pub particle_size_vel_influence: f32,
+    #[range(0.005, 0.015)]
+    pub particle_min_size: f32,
+    #[range(0.015, 0.03)]
+    pub particle_max_size: f32,
+    #[range(0.0, 0.02)]
+    pub particle_shrink_speed: f32,
+    pub particle_start_center: Vec2,
+    #[range(0.2, 0.8)]
+    pub particle_start_distance: f32,
+    #[range(0.5, 1.0)]
+    pub particle_inner_color: LinearRgba,
+    #[range(0.5, 1.0)]
+    pub particle_border_color: LinearRgba,
+}
 
-impl Material for SugarCoatMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/sugarcoat.wgsl".into()
+impl Default for SugarCoatParams {
+    fn default() -> Self {
+        Self {
+            particle_amount: 50,
+            particle_seed: 69,
+            particle_gravity: 2.5,
+            particle_min_angle: -PI / 6.0,
+            particle_max_angle: PI * 0.2,
+            particle_min_velocity: 0.5,
+            particle_max_velocity: 1.7,
+            particle_size_vel_influence: 0.5,
+            particle_min_size: 0.015,
+            particle_max_size: 0.02,
+            particle_shrink_speed: 0.005,
+            particle_start_center: Vec2::new(-0.3, 0.2),
+            particle_start_distance: 0.5,
+            particle_inner_color: LinearRgba::rgb(0.9, 0.85, 0.85),
+            particle_border_color: LinearRgba::rgb(0.7, 0.65, 0.65),
+        }
     }
+}
+
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/sugarcoat.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct SugarCoatMaterial {
+    #[uniform(0)]
+    pub params: SugarCoatParams,
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+impl Default for SugarCoatMaterial {
+    fn default() -> Self {
+        Self {
+            params: default(),
+            clock: EffectClock::with_cycle(3.0),
+        }
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct BillBurstMaterial {}
+impl ShaderEntry for SugarCoatMaterial {
+    const NAME: &'static str = "Sugarcoat";
+    const DESCRIPTION: &'static str = "Sprinkles thrown out and falling";
+    const TAGS: &'static [&'static str] = &["particles"];
+}
 
-impl Material for BillBurstMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/bill-burst.wgsl".into()
+#[derive(Debug, Clone, Reflect, ShaderType, Deserialize, ParamRanges)]
+#[reflect(ParamRanges)]
+#[serde(default)]
+pub struct BillBurstParams {
+    #[range(5.0, 50.0)]
+    pub particle_count: i32,
+    #[range(0.05, 0.25)]
+    pub particle_base_width: f32,
+    #[range(0.03, 0.12)]
+    pub particle_base_height: f32,
+    #[range(2.0, 8.0)]
+    pub particle_velocity: f32,
+    pub particle_gravity: Vec2,
+    pub dollar_green: LinearRgba,
+    pub dark_green: LinearRgba,
+}
+
+impl Default for BillBurstParams {
+    fn default() -> Self {
+        Self {
+            particle_count: 25,
+            particle_base_width: 0.15,
+            particle_base_height: 0.07,
+            particle_velocity: 5.0,
+            particle_gravity: Vec2::new(0.0, -7.0),
+            dollar_green: LinearRgba::new(133.0 / 255.0, 187.0 / 255.0, 101.0 / 255.0, 1.0),
+            dark_green: LinearRgba::new(50.0 / 255.0, 77.0 / 255.0, 34.0 / 255.0, 1.0),
+        }
     }
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/bill-burst.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct BillBurstMaterial {
+    #[uniform(0)]
+    pub params: BillBurstParams,
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
+}
+
+impl Default for BillBurstMaterial {
+    fn default() -> Self {
+        Self {
+            params: default(),
+            clock: EffectClock::with_cycle(3.0),
+        }
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+impl ShaderEntry for BillBurstMaterial {
+    const NAME: &'static str = "Bill burst";
+    const DESCRIPTION: &'static str = "Bank notes bursting out and fluttering down";
+    const TAGS: &'static [&'static str] = &["particles"];
+}
+
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/particles.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
 pub struct ParticlesMaterial {
     #[uniform(0)]
     pub effect: UVec4,
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
 }
 
-impl Material for ParticlesMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/particles.wgsl".into()
+impl Default for ParticlesMaterial {
+    fn default() -> Self {
+        Self {
+            effect: UVec4::ZERO,
+            clock: EffectClock::with_cycle(3.0),
+        }
     }
+}
+
+impl ShaderEntry for ParticlesMaterial {
+    const NAME: &'static str = "Particles";
+    const DESCRIPTION: &'static str = "Rings or hearts popping up at random";
+    const TAGS: &'static [&'static str] = &["particles"];
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/droplet.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct DropletMaterial {
+    #[uniform(0)]
+    #[clock]
+    pub clock: EffectClock,
+}
+
+impl Default for DropletMaterial {
+    fn default() -> Self {
+        Self {
+            clock: EffectClock::with_cycle(3.0),
+        }
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct DropletMaterial {}
+impl ShaderEntry for DropletMaterial {
+    const NAME: &'static str = "Droplet";
+    const DESCRIPTION: &'static str = "A raindrop shape built from geometry";
+    const TAGS: &'static [&'static str] = &["test"];
+}
+
+#[derive(Debug, Clone, Reflect, ShaderType, Deserialize, ParamRanges)]
+#[reflect(ParamRanges)]
+#[serde(default)]
+pub struct MandelbrotParams {
+    #[range(10.0, 100.0)]
+    pub max_iters: f32,
+    // Padding for WASM, uniforms need to be a multiple of 16 bytes
+    #[serde(skip)]
+    #[reflect(skip_serializing)]
+    _padding: Vec3,
+}
 
-impl Material for DropletMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/droplet.wgsl".into()
+impl Default for MandelbrotParams {
+    fn default() -> Self {
+        Self {
+            max_iters: 50.0,
+            _padding: Vec3::ZERO,
+        }
     }
+}
+
+#[derive(Asset, Reflect, AsBindGroup, Debug, Clone, Deserialize, ShaderMaterial)]
+#[shader("embedded://bevy_shader_testing/shaders/mandelbrot.wgsl", alpha = blend)]
+#[bind_group_data(DebugView)]
+#[serde(default)]
+pub struct MandelbrotMaterial {
+    #[uniform(0)]
+    pub params: MandelbrotParams,
+    #[uniform(1)]
+    #[clock]
+    pub clock: EffectClock,
+    /// Colors from the background at the left to the edge of the set at the right
+    #[texture(2)]
+    #[sampler(3)]
+    #[serde(skip)]
+    #[reflect(ignore, clone)]
+    pub gradient: Option<Handle<Image>>,
+}
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+impl Default for MandelbrotMaterial {
+    fn default() -> Self {
+        Self {
+            params: default(),
+            clock: EffectClock::with_cycle(4.0),
+            gradient: Some(MANDELBROT_GRADIENT),
+        }
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct MandelbrotMaterial {}
+impl ShaderEntry for MandelbrotMaterial {
+    const NAME: &'static str = "Mandelbrot";
+    const DESCRIPTION: &'static str = "Zooming into the Mandelbrot set";
+    const TAGS: &'static [&'static str] = &["fractal", "background", "gradient"];
+}
 
-impl Material for MandelbrotMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/mandelbrot.wgsl".into()
+/// Lets the manifest list only the points in use, as 3d points
+fn control_points<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[Vec4; 16], D::Error> {
+    let points = Vec::<Vec3>::deserialize(deserializer)?;
+    if points.len() > 16 {
+        return Err(serde::de::Error::invalid_length(
+            points.len(),
+            &"at most 16 control points",
+        ));
     }
+    Ok(pad_to(points, 16).as_slice().try_into().unwrap())
+}
+
+fn pad_to(input: Vec<Vec3>, desired_len: usize) -> Vec<Vec4> {
+    let padding = std::iter::repeat_n(Vec3::default(), desired_len - input.len());
+    input
+        .into_iter()
+        .chain(padding)
+        .map(|v| v.extend(0.0))
+        .collect::<Vec<_>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ripple_ring_spreads_out() {
+        let mut ripple = RippleMaterial::default();
+        let center = Vec2::splat(0.5);
+        // Halfway between the center and the edge
+        let halfway = Vec2::new(0.75, 0.5);
+
+        assert_eq!(ripple.height(center), ripple.params.max_height);
+        assert!(ripple.height(halfway) < ripple.height(center));
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+        ripple.clock.elapsed = 0.1 * ripple.clock.cycle;
+        assert!(ripple.height(halfway) > ripple.height(center));
     }
 }
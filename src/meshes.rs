@@ -0,0 +1,133 @@
+use bevy::{input::ButtonState, prelude::*};
+
+use crate::{manifest::MeshSpec, search::Hotkeys, selected_tile, styled_button, SQUARE_EDGE};
+
+const GLTF_PATH: &str = "meshes/crystal.gltf";
+
+/// Swaps the selected tile's mesh, to see how its effect handles other UV
+/// layouts and normals before putting it on game geometry
+///
+/// N or the mesh button steps through a quad, a subdivided plane, a cube, a
+/// sphere, a cylinder, a torus and `assets/meshes/crystal.gltf`, then back to
+/// the tile's own mesh.
+pub struct MeshesPlugin;
+
+impl Plugin for MeshesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_meshes)
+            .add_systems(Update, (mesh_keys, mesh_button_presses));
+    }
+}
+
+#[derive(Debug, Resource)]
+struct PreviewMeshes(Vec<(&'static str, Handle<Mesh>)>);
+
+/// The mesh a tile had before switching, and which preview mesh it shows now
+#[derive(Debug, Component)]
+struct SwitchedMesh {
+    own: Handle<Mesh>,
+    index: usize,
+}
+
+#[derive(Debug, Component)]
+struct MeshButton;
+
+/// Steps the selected tile to the next mesh, like N
+pub fn mesh_button() -> impl Bundle {
+    (styled_button("mesh"), MeshButton)
+}
+
+fn load_meshes(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let specs = [
+        ("quad", MeshSpec::Rectangle(SQUARE_EDGE, SQUARE_EDGE)),
+        (
+            "plane",
+            MeshSpec::Plane {
+                size: SQUARE_EDGE,
+                subdivisions: 20,
+            },
+        ),
+        ("cube", MeshSpec::Cuboid(0.15)),
+        ("sphere", MeshSpec::Sphere(0.125)),
+        (
+            "cylinder",
+            MeshSpec::Cylinder {
+                radius: 0.1,
+                height: 0.2,
+                caps: true,
+            },
+        ),
+        (
+            "torus",
+            MeshSpec::Torus {
+                inner_radius: 0.05,
+                outer_radius: 0.125,
+            },
+        ),
+    ];
+    let mut previews = specs
+        .iter()
+        .map(|(name, spec)| (*name, meshes.add(spec)))
+        .collect::<Vec<_>>();
+    previews.push((
+        "glTF",
+        asset_server.load(
+            GltfAssetLabel::Primitive {
+                mesh: 0,
+                primitive: 0,
+            }
+            .from_asset(GLTF_PATH),
+        ),
+    ));
+    commands.insert_resource(PreviewMeshes(previews));
+}
+
+fn mesh_keys(mut commands: Commands, mut hotkeys: Hotkeys) {
+    for event in hotkeys.read() {
+        if event.repeat || event.state == ButtonState::Released || event.key_code != KeyCode::KeyN {
+            continue;
+        }
+        commands.queue(next_mesh);
+    }
+}
+
+fn mesh_button_presses(
+    mut commands: Commands,
+    buttons: Query<&Interaction, (Changed<Interaction>, With<MeshButton>)>,
+) {
+    for interaction in &buttons {
+        if *interaction == Interaction::Pressed {
+            commands.queue(next_mesh);
+        }
+    }
+}
+
+fn next_mesh(world: &mut World) {
+    let Some(tile) = selected_tile(world) else {
+        return;
+    };
+    let previews = world.resource::<PreviewMeshes>().0.clone();
+    let mut tile = world.entity_mut(tile);
+    let Some(Mesh3d(current)) = tile.get::<Mesh3d>().cloned() else {
+        return;
+    };
+
+    let (own, next) = match tile.take::<SwitchedMesh>() {
+        Some(switched) => (switched.own, switched.index + 1),
+        None => (current, 0),
+    };
+    match previews.get(next) {
+        Some((name, mesh)) => {
+            info!("Showing the effect on a {name}");
+            tile.insert((Mesh3d(mesh.clone()), SwitchedMesh { own, index: next }));
+        }
+        None => {
+            info!("Showing the effect on its own mesh");
+            tile.insert(Mesh3d(own));
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use bevy::{input::ButtonState, prelude::*};
+use bevy_shader_testing::{ranges::randomize, registry::MaterialAccess};
+use fastrand::Rng;
+
+use crate::{search::Hotkeys, selected_tile};
+
+/// Randomizes the selected tile within the ranges its material declares
+///
+/// M makes a new variant, and brackets step back and forward through the ones
+/// made so far. Going back and mutating again drops the variants after it, the
+/// way a browser's history does, so save a good one as a preset first.
+pub struct MutatePlugin;
+
+impl Plugin for MutatePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, mutate_keys);
+    }
+}
+
+/// Variants of a tile's material, starting with the one it had before mutating
+#[derive(Debug, Component)]
+struct History {
+    variants: Vec<Box<dyn Reflect>>,
+    current: usize,
+}
+
+fn mutate_keys(mut commands: Commands, mut hotkeys: Hotkeys) {
+    for event in hotkeys.read() {
+        if event.repeat || event.state == ButtonState::Released {
+            continue;
+        }
+
+        match event.key_code {
+            KeyCode::KeyM => commands.queue(mutate),
+            KeyCode::BracketLeft => commands.queue(|world: &mut World| step_history(world, -1)),
+            KeyCode::BracketRight => commands.queue(|world: &mut World| step_history(world, 1)),
+            _ => {}
+        }
+    }
+}
+
+fn mutate(world: &mut World) {
+    let Some(tile) = selected_tile(world) else {
+        return;
+    };
+    let Some(access) = world.get::<MaterialAccess>(tile).copied() else {
+        return;
+    };
+    let Some(Ok(before)) = access
+        .get(world, tile)
+        .map(|material| material.reflect_clone())
+    else {
+        return;
+    };
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let mut rng = Rng::new();
+    access.edit(world, tile, |material| {
+        randomize(
+            material.as_partial_reflect_mut(),
+            &registry.read(),
+            &mut rng,
+        );
+    });
+    let Some(Ok(after)) = access
+        .get(world, tile)
+        .map(|material| material.reflect_clone())
+    else {
+        return;
+    };
+    if after.reflect_partial_eq(before.as_partial_reflect()) == Some(true) {
+        info!(
+            "{} has no #[range] fields to mutate",
+            before.reflect_short_type_path()
+        );
+        return;
+    }
+
+    if !world.entity(tile).contains::<History>() {
+        world.entity_mut(tile).insert(History {
+            variants: vec![before],
+            current: 0,
+        });
+    }
+    let mut history = world.get_mut::<History>(tile).unwrap();
+    let current = history.current;
+    history.variants.truncate(current + 1);
+    history.variants.push(after);
+    history.current = history.variants.len() - 1;
+    info!("Variant {}", history.current);
+}
+
+fn step_history(world: &mut World, step: isize) {
+    let Some(tile) = selected_tile(world) else {
+        return;
+    };
+    let Some(access) = world.get::<MaterialAccess>(tile).copied() else {
+        return;
+    };
+    let Some(mut history) = world.get_mut::<History>(tile) else {
+        return;
+    };
+    let Some(current) = history
+        .current
+        .checked_add_signed(step)
+        .filter(|&index| index < history.variants.len())
+    else {
+        return;
+    };
+    history.current = current;
+    let Ok(variant) = history.variants[current].reflect_clone() else {
+        return;
+    };
+
+    access.edit(world, tile, |material| {
+        material.apply(variant.as_partial_reflect());
+    });
+    match current {
+        0 => info!("Back to before mutating"),
+        _ => info!("Variant {current}"),
+    }
+}
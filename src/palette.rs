@@ -0,0 +1,229 @@
+//! Character color themes shared by the effects
+//!
+//! Insert a [`VfxPalette`] and every [`Themed`] material is recolored from it,
+//! including ones added later. Changing the resource recolors them all again,
+//! removing it puts back the colors they had before the first palette.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::Deserialize;
+
+use crate::materials::*;
+
+/// The colors of one character or team
+#[derive(Debug, Clone, PartialEq, Resource, Reflect, Deserialize)]
+pub struct VfxPalette {
+    /// The main body of an effect
+    pub primary: LinearRgba,
+    /// Rims and outer layers
+    pub secondary: LinearRgba,
+    /// The hot center of a hit, usually close to white
+    pub highlight: LinearRgba,
+    /// Layers behind the rest, usually a darker primary
+    pub shadow: LinearRgba,
+}
+
+/// A material whose color fields are filled from the [`VfxPalette`]
+pub trait Themed: Material {
+    /// The fields `apply_palette` overwrites
+    type Colors: Send + Sync + 'static;
+
+    fn colors(&self) -> Self::Colors;
+    fn set_colors(&mut self, colors: Self::Colors);
+    fn apply_palette(&mut self, palette: &VfxPalette);
+}
+
+/// Recolors every `M` when the palette changes, and new ones as they're added
+///
+/// Each material's own colors are kept from the first time it's recolored, and
+/// written back once the palette is removed.
+pub fn apply_palette<M: Themed>(
+    palette: Option<Res<VfxPalette>>,
+    mut events: MessageReader<AssetEvent<M>>,
+    mut materials: ResMut<Assets<M>>,
+    mut originals: Local<HashMap<AssetId<M>, M::Colors>>,
+) {
+    let mut added = Vec::new();
+    for event in events.read() {
+        match *event {
+            AssetEvent::Added { id } => added.push(id),
+            AssetEvent::Removed { id } => {
+                originals.remove(&id);
+            }
+            _ => {}
+        }
+    }
+
+    let Some(palette) = palette else {
+        for (id, colors) in originals.drain() {
+            if let Some(material) = materials.get_mut(id) {
+                material.set_colors(colors);
+            }
+        }
+        return;
+    };
+
+    let recolor = if palette.is_changed() {
+        materials.ids().collect()
+    } else {
+        added
+    };
+    for id in recolor {
+        if let Some(material) = materials.get_mut(id) {
+            originals.entry(id).or_insert_with(|| material.colors());
+            material.apply_palette(&palette);
+        }
+    }
+}
+
+impl Themed for HitSparkMaterial {
+    type Colors = [LinearRgba; 3];
+
+    fn colors(&self) -> Self::Colors {
+        [self.base_color, self.mid_color, self.edge_color]
+    }
+
+    fn set_colors(&mut self, [base_color, mid_color, edge_color]: Self::Colors) {
+        self.base_color = base_color;
+        self.mid_color = mid_color;
+        self.edge_color = edge_color;
+    }
+
+    fn apply_palette(&mut self, palette: &VfxPalette) {
+        self.base_color = palette.highlight;
+        self.mid_color = palette.primary;
+        self.edge_color = palette.secondary;
+    }
+}
+
+impl Themed for BlockMaterial {
+    type Colors = [LinearRgba; 2];
+
+    fn colors(&self) -> Self::Colors {
+        [self.base_color, self.edge_color]
+    }
+
+    fn set_colors(&mut self, [base_color, edge_color]: Self::Colors) {
+        self.base_color = base_color;
+        self.edge_color = edge_color;
+    }
+
+    fn apply_palette(&mut self, palette: &VfxPalette) {
+        self.base_color = palette.highlight;
+        self.edge_color = palette.primary;
+    }
+}
+
+impl Themed for ClinkMaterial {
+    type Colors = [LinearRgba; 2];
+
+    fn colors(&self) -> Self::Colors {
+        [self.base_color, self.edge_color]
+    }
+
+    fn set_colors(&mut self, [base_color, edge_color]: Self::Colors) {
+        self.base_color = base_color;
+        self.edge_color = edge_color;
+    }
+
+    fn apply_palette(&mut self, palette: &VfxPalette) {
+        self.base_color = palette.secondary;
+        self.edge_color = palette.primary;
+    }
+}
+
+impl Themed for RippleRingMaterial {
+    type Colors = [LinearRgba; 2];
+
+    fn colors(&self) -> Self::Colors {
+        [self.base_color, self.edge_color]
+    }
+
+    fn set_colors(&mut self, [base_color, edge_color]: Self::Colors) {
+        self.base_color = base_color;
+        self.edge_color = edge_color;
+    }
+
+    fn apply_palette(&mut self, palette: &VfxPalette) {
+        self.base_color = palette.primary;
+        self.edge_color = palette.highlight;
+    }
+}
+
+impl Themed for LineFieldMaterial {
+    type Colors = [LinearRgba; 2];
+
+    fn colors(&self) -> Self::Colors {
+        [self.base_color, self.edge_color]
+    }
+
+    fn set_colors(&mut self, [base_color, edge_color]: Self::Colors) {
+        self.base_color = base_color;
+        self.edge_color = edge_color;
+    }
+
+    fn apply_palette(&mut self, palette: &VfxPalette) {
+        self.base_color = palette.shadow;
+        self.edge_color = palette.primary;
+    }
+}
+
+impl Themed for MultiRippleRingMaterial {
+    type Colors = [LinearRgba; 2];
+
+    fn colors(&self) -> Self::Colors {
+        [self.base_color, self.edge_color]
+    }
+
+    fn set_colors(&mut self, [base_color, edge_color]: Self::Colors) {
+        self.base_color = base_color;
+        self.edge_color = edge_color;
+    }
+
+    fn apply_palette(&mut self, palette: &VfxPalette) {
+        self.base_color = palette.secondary;
+        self.edge_color = palette.highlight;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removing_the_palette_restores_colors() {
+        let mut app = App::new();
+        app.init_resource::<Assets<BlockMaterial>>()
+            .add_message::<AssetEvent<BlockMaterial>>()
+            .add_systems(Update, apply_palette::<BlockMaterial>);
+
+        let own = BlockMaterial {
+            base_color: LinearRgba::RED,
+            edge_color: LinearRgba::BLUE,
+            ..default()
+        };
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<BlockMaterial>>()
+            .add(own.clone());
+        let colors = |app: &App| {
+            app.world()
+                .resource::<Assets<BlockMaterial>>()
+                .get(&handle)
+                .unwrap()
+                .colors()
+        };
+
+        app.insert_resource(VfxPalette {
+            primary: LinearRgba::GREEN,
+            secondary: LinearRgba::WHITE,
+            highlight: LinearRgba::BLACK,
+            shadow: LinearRgba::WHITE,
+        });
+        app.update();
+        assert_eq!(colors(&app), [LinearRgba::BLACK, LinearRgba::GREEN]);
+
+        app.world_mut().remove_resource::<VfxPalette>();
+        app.update();
+        assert_eq!(colors(&app), own.colors());
+    }
+}
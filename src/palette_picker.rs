@@ -0,0 +1,108 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    input::ButtonState,
+    prelude::*,
+};
+use bevy_shader_testing::palette::VfxPalette;
+use serde::Deserialize;
+
+use crate::search::Hotkeys;
+
+const PALETTES_PATH: &str = "characters.palettes.ron";
+
+/// Loads the character palettes from `assets/characters.palettes.ron`, C
+/// cycles the whole grid through them
+///
+/// After the last palette comes the gallery's own colors again, with any edits
+/// made in the meantime kept.
+pub struct PalettePickerPlugin;
+
+impl Plugin for PalettePickerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<CharacterPalettes>()
+            .init_asset_loader::<CharacterPalettesLoader>()
+            .init_resource::<SelectedPalette>()
+            .add_systems(Startup, load_palettes)
+            .add_systems(Update, palette_keys);
+    }
+}
+
+#[derive(Asset, TypePath, Debug, Deserialize)]
+struct CharacterPalettes {
+    palettes: Vec<NamedPalette>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct NamedPalette {
+    name: String,
+    colors: VfxPalette,
+}
+
+#[derive(Default)]
+struct CharacterPalettesLoader;
+
+impl AssetLoader for CharacterPalettesLoader {
+    type Asset = CharacterPalettes;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<CharacterPalettes, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["palettes.ron"]
+    }
+}
+
+#[derive(Debug, Resource)]
+struct Palettes(Handle<CharacterPalettes>);
+
+/// Index into the palettes, or `None` for the gallery's own colors
+#[derive(Debug, Default, Resource)]
+struct SelectedPalette(Option<usize>);
+
+fn load_palettes(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Palettes(asset_server.load(PALETTES_PATH)));
+}
+
+fn palette_keys(
+    mut commands: Commands,
+    mut hotkeys: Hotkeys,
+    palettes: Res<Palettes>,
+    assets: Res<Assets<CharacterPalettes>>,
+    mut selected: ResMut<SelectedPalette>,
+) {
+    for event in hotkeys.read() {
+        if event.repeat || event.state == ButtonState::Released || event.key_code != KeyCode::KeyC {
+            continue;
+        }
+        let Some(palettes) = assets.get(&palettes.0) else {
+            continue;
+        };
+
+        let next = match selected.0 {
+            None => 0,
+            Some(index) => index + 1,
+        };
+        match palettes.palettes.get(next) {
+            Some(palette) => {
+                info!("{} palette", palette.name);
+                selected.0 = Some(next);
+                commands.insert_resource(palette.colors.clone());
+            }
+            None => {
+                info!("Gallery colors");
+                selected.0 = None;
+                commands.remove_resource::<VfxPalette>();
+            }
+        }
+    }
+}
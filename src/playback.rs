@@ -0,0 +1,299 @@
+use bevy::{input::ButtonState, prelude::*, ui::RelativeCursorPosition};
+use bevy_shader_testing::clock::{
+    advance_effect_time, ClockAccess, EffectClock, EffectTime, FrameClock, TickEffectClocks,
+};
+
+use crate::{search::Hotkeys, selected_tile, styled_button, BORDER_COLOR, PRESSED_BUTTON};
+
+const SPEEDS: [f32; 7] = [0.1, 0.25, 0.5, 1.0, 1.5, 2.0, 4.0];
+const FPS: u32 = 60;
+const SCRUB_BAR_WIDTH: f32 = 400.0;
+
+/// Pausing, frame stepping, scrubbing and speed for every effect at once
+///
+/// Everything goes through [`EffectTime`], so the tiles stay in sync. The scrub
+/// bar follows the cycle of the selected tile, which can also be triggered to
+/// play a single cycle, the way a hit effect plays in a game.
+///
+/// Frame locking swaps real time for a [`FrameClock`] at [`FPS`], the way a
+/// game with rollback would drive the effects.
+pub struct PlaybackPlugin;
+
+impl Plugin for PlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectedClock>().add_systems(
+            Update,
+            (
+                (playback_keys, playback_buttons, scrub).before(TickEffectClocks),
+                advance_frame.before(advance_effect_time),
+                (read_selected_clock, update_playback_ui)
+                    .chain()
+                    .after(TickEffectClocks),
+            ),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+enum PlaybackButton {
+    StepBack,
+    PlayPause,
+    StepForward,
+    Slower,
+    Faster,
+    Trigger,
+    ToggleLoop,
+    ToggleFrameLock,
+}
+
+impl PlaybackButton {
+    fn apply(self, world: &mut World) {
+        let mut time = world.resource_mut::<EffectTime>();
+        match self {
+            PlaybackButton::StepBack => {
+                time.paused = true;
+                time.step(-1);
+            }
+            PlaybackButton::PlayPause => time.paused = !time.paused,
+            PlaybackButton::StepForward => {
+                time.paused = true;
+                time.step(1);
+            }
+            PlaybackButton::Slower => change_speed(&mut time, -1),
+            PlaybackButton::Faster => change_speed(&mut time, 1),
+            PlaybackButton::Trigger => {
+                modify_selected_clock(world, |clock, now| clock.trigger(now));
+            }
+            PlaybackButton::ToggleLoop => modify_selected_clock(world, |clock, _| {
+                clock.looping = u32::from(clock.looping == 0);
+            }),
+            PlaybackButton::ToggleFrameLock => {
+                time.frames = match time.frames {
+                    Some(_) => None,
+                    None => Some(FrameClock { frame: 0, fps: FPS }),
+                };
+                // Pick up from the same point
+                let elapsed = time.elapsed;
+                time.seek(elapsed);
+            }
+        }
+    }
+}
+
+fn change_speed(time: &mut EffectTime, steps: i32) {
+    let current = SPEEDS
+        .iter()
+        .position(|&speed| speed >= time.speed)
+        .unwrap_or(SPEEDS.len() - 1);
+    let index = (current as i32 + steps).clamp(0, SPEEDS.len() as i32 - 1);
+    time.speed = SPEEDS[index as usize];
+}
+
+#[derive(Debug, Component)]
+struct ScrubBar;
+
+#[derive(Debug, Component)]
+struct ScrubFill;
+
+#[derive(Debug, Component)]
+struct PlaybackLabel;
+
+/// Clock of the selected tile, as of this frame
+#[derive(Debug, Default, Resource)]
+struct SelectedClock(Option<EffectClock>);
+
+/// Scrub bar and buttons, laid out under the selected preview
+pub fn playback_controls() -> impl Bundle {
+    (
+        Node {
+            align_self: AlignSelf::FlexEnd,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(5.0),
+            ..default()
+        },
+        children![
+            (Text::new(""), PlaybackLabel),
+            (
+                ScrubBar,
+                Interaction::default(),
+                RelativeCursorPosition::default(),
+                Node {
+                    width: Val::Px(SCRUB_BAR_WIDTH),
+                    height: Val::Px(14.0),
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                BorderColor::all(BORDER_COLOR),
+                BackgroundColor(Color::BLACK),
+                children![(
+                    ScrubFill,
+                    Node {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(PRESSED_BUTTON),
+                )],
+            ),
+            (
+                Node {
+                    column_gap: Val::Px(5.0),
+                    ..default()
+                },
+                children![
+                    (styled_button("<"), PlaybackButton::StepBack),
+                    (styled_button("pause"), PlaybackButton::PlayPause),
+                    (styled_button(">"), PlaybackButton::StepForward),
+                    (styled_button("slower"), PlaybackButton::Slower),
+                    (styled_button("faster"), PlaybackButton::Faster),
+                    (styled_button("trigger"), PlaybackButton::Trigger),
+                    (styled_button("loop"), PlaybackButton::ToggleLoop),
+                    (
+                        styled_button("lock frames"),
+                        PlaybackButton::ToggleFrameLock
+                    ),
+                ],
+            ),
+        ],
+    )
+}
+
+fn playback_keys(mut commands: Commands, mut hotkeys: Hotkeys) {
+    for event in hotkeys.read() {
+        if event.state == ButtonState::Released {
+            continue;
+        }
+
+        let action = match event.key_code {
+            KeyCode::Comma => PlaybackButton::StepBack,
+            KeyCode::Period => PlaybackButton::StepForward,
+            // Holding the step keys keeps stepping, the rest act once per press
+            _ if event.repeat => continue,
+            KeyCode::Space => PlaybackButton::PlayPause,
+            KeyCode::Minus => PlaybackButton::Slower,
+            KeyCode::Equal => PlaybackButton::Faster,
+            KeyCode::KeyT => PlaybackButton::Trigger,
+            KeyCode::KeyL => PlaybackButton::ToggleLoop,
+            KeyCode::KeyF => PlaybackButton::ToggleFrameLock,
+            _ => continue,
+        };
+        commands.queue(move |world: &mut World| action.apply(world));
+    }
+}
+
+fn playback_buttons(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &PlaybackButton), Changed<Interaction>>,
+) {
+    for (interaction, &action) in &buttons {
+        if *interaction == Interaction::Pressed {
+            commands.queue(move |world: &mut World| action.apply(world));
+        }
+    }
+}
+
+/// Moves the timeline so the selected tile is where the cursor is on the bar
+fn scrub(
+    bar: Query<(&Interaction, &RelativeCursorPosition), With<ScrubBar>>,
+    selected: Res<SelectedClock>,
+    mut time: ResMut<EffectTime>,
+) {
+    let Ok((interaction, cursor)) = bar.single() else {
+        return;
+    };
+    if *interaction != Interaction::Pressed {
+        return;
+    }
+    let (Some(cursor), Some(clock)) = (cursor.normalized, selected.0) else {
+        return;
+    };
+    if clock.speed <= 0.0 {
+        return;
+    }
+
+    // The cursor goes from -0.5 to 0.5 across the bar
+    let fraction = (cursor.x + 0.5).clamp(0.0, 1.0);
+    let elapsed = (clock.cycle_index() + fraction) * clock.cycle;
+    time.seek(clock.start + elapsed / clock.speed);
+}
+
+/// Stands in for a game's simulation, one frame per update while frame locked
+fn advance_frame(mut time: ResMut<EffectTime>) {
+    if time.paused {
+        return;
+    }
+    if let Some(frames) = &mut time.frames {
+        frames.frame += 1;
+    }
+}
+
+fn selected_clock(world: &mut World) -> Option<(Entity, ClockAccess)> {
+    let entity = selected_tile(world)?;
+    Some((entity, *world.get::<ClockAccess>(entity)?))
+}
+
+/// Changes the selected tile's clock, given the current [`EffectTime`]
+fn modify_selected_clock(world: &mut World, modify: impl FnOnce(&mut EffectClock, f32)) {
+    let Some((entity, access)) = selected_clock(world) else {
+        return;
+    };
+    let Some(mut clock) = access.get(world, entity) else {
+        return;
+    };
+    modify(&mut clock, world.resource::<EffectTime>().elapsed);
+    access.set(world, entity, clock);
+}
+
+fn read_selected_clock(world: &mut World) {
+    let clock = selected_clock(world).and_then(|(entity, access)| access.get(world, entity));
+    world.resource_mut::<SelectedClock>().0 = clock;
+}
+
+fn update_playback_ui(
+    time: Res<EffectTime>,
+    selected: Res<SelectedClock>,
+    mut fill: Query<&mut Node, With<ScrubFill>>,
+    mut label: Query<&mut Text, With<PlaybackLabel>>,
+    buttons: Query<(&PlaybackButton, &Children)>,
+    mut texts: Query<&mut Text, Without<PlaybackLabel>>,
+) {
+    let (cycle_time, cycle, looping) = selected.0.map_or((0.0, 0.0, true), |clock| {
+        (clock.cycle_time(), clock.cycle, clock.looping != 0)
+    });
+
+    if let Ok(mut fill) = fill.single_mut() {
+        let progress = if cycle > 0.0 { cycle_time / cycle } else { 0.0 };
+        let width = Val::Percent(100.0 * progress);
+        if fill.width != width {
+            fill.width = width;
+        }
+    }
+
+    if let Ok(mut label) = label.single_mut() {
+        let rate = match time.frames {
+            Some(frames) => format!("frame {}", frames.frame),
+            None => format!("{}x", time.speed),
+        };
+        let state = if time.paused { "  paused" } else { "" };
+        label.set_if_neq(Text(format!(
+            "{cycle_time:.2}s / {cycle:.2}s  {rate}{state}"
+        )));
+    }
+
+    for (action, children) in &buttons {
+        // Buttons that toggle show what pressing them will do
+        let label = match action {
+            PlaybackButton::PlayPause if time.paused => "play",
+            PlaybackButton::PlayPause => "pause",
+            PlaybackButton::ToggleLoop if looping => "play once",
+            PlaybackButton::ToggleLoop => "loop",
+            PlaybackButton::ToggleFrameLock if time.frames.is_some() => "unlock frames",
+            PlaybackButton::ToggleFrameLock => "lock frames",
+            _ => continue,
+        };
+        if let Ok(mut text) = texts.get_mut(children[0]) {
+            text.set_if_neq(Text::new(label));
+        }
+    }
+}
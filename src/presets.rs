@@ -0,0 +1,246 @@
+use std::{fs, path::PathBuf};
+
+use bevy::{
+    asset::io::file::FileAssetReader,
+    input::ButtonState,
+    platform::collections::HashMap,
+    prelude::*,
+    reflect::{
+        serde::{ReflectDeserializer, ReflectSerializer},
+        TypeRegistry,
+    },
+};
+use bevy_shader_testing::{
+    clock::{ClockAccess, EffectTime},
+    registry::MaterialAccess,
+};
+use ron::ser::PrettyConfig;
+use serde::de::DeserializeSeed;
+
+use crate::{search::Hotkeys, selected_tile};
+
+const PRESET_FOLDER: &str = "presets";
+
+/// Named material values, saved in `assets/presets/<material>/<name>.ron`
+///
+/// Presets are written through `Reflect`, so every registered material can be
+/// saved and the file records which material it belongs to. They're read once
+/// at startup, P cycles through the ones for the selected tile and Shift+P saves
+/// the selected tile as a new one.
+pub struct PresetsPlugin;
+
+impl Plugin for PresetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Presets>()
+            .add_systems(Startup, load_presets)
+            .add_systems(Update, preset_keys);
+    }
+}
+
+/// Every preset, by the type path of its material
+#[derive(Debug, Default, Resource)]
+struct Presets(HashMap<String, Vec<Preset>>);
+
+#[derive(Debug)]
+struct Preset {
+    name: String,
+    value: Box<dyn PartialReflect>,
+}
+
+/// Which of its material's presets a tile is showing
+#[derive(Debug, Component)]
+struct PresetIndex(usize);
+
+fn presets_path() -> PathBuf {
+    FileAssetReader::get_base_path()
+        .join("assets")
+        .join(PRESET_FOLDER)
+}
+
+/// Folder for a material's presets, `RippleRingMaterial` goes in `ripple_ring`
+fn preset_folder(short_type_path: &str) -> String {
+    let name = short_type_path
+        .strip_suffix("Material")
+        .unwrap_or(short_type_path);
+    let mut folder = String::new();
+    for (index, char) in name.chars().enumerate() {
+        if char.is_uppercase() && index > 0 {
+            folder.push('_');
+        }
+        folder.push(char.to_ascii_lowercase());
+    }
+    folder
+}
+
+fn read_preset(path: &PathBuf, registry: &TypeRegistry) -> Result<Box<dyn PartialReflect>> {
+    let text = fs::read_to_string(path)?;
+    let mut deserializer = ron::Deserializer::from_str(&text)?;
+    Ok(ReflectDeserializer::new(registry).deserialize(&mut deserializer)?)
+}
+
+fn load_presets(registry: Res<AppTypeRegistry>, mut presets: ResMut<Presets>) {
+    // Nothing has been saved yet
+    let Ok(folders) = fs::read_dir(presets_path()) else {
+        return;
+    };
+    let registry = registry.read();
+
+    for folder in folders.flatten() {
+        let Ok(files) = fs::read_dir(folder.path()) else {
+            continue;
+        };
+        for file in files.flatten() {
+            let path = file.path();
+            if path.extension().is_none_or(|extension| extension != "ron") {
+                continue;
+            }
+
+            let value = match read_preset(&path, &registry) {
+                Ok(value) => value,
+                Err(err) => {
+                    warn!("Couldn't load preset {}: {err}", path.display());
+                    continue;
+                }
+            };
+            let Some(info) = value.get_represented_type_info() else {
+                continue;
+            };
+            let material = info.type_path_table();
+            if folder.file_name() != preset_folder(material.short_path()).as_str() {
+                warn!(
+                    "Preset {} is for {}, which goes in {}",
+                    path.display(),
+                    material.short_path(),
+                    preset_folder(material.short_path())
+                );
+                continue;
+            }
+
+            let name = path.file_stem().unwrap().to_string_lossy().to_string();
+            presets
+                .0
+                .entry(material.path().to_string())
+                .or_default()
+                .push(Preset { name, value });
+        }
+    }
+
+    for presets in presets.0.values_mut() {
+        presets.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+}
+
+fn preset_keys(mut commands: Commands, mut hotkeys: Hotkeys, keys: Res<ButtonInput<KeyCode>>) {
+    for event in hotkeys.read() {
+        if event.repeat || event.state == ButtonState::Released {
+            continue;
+        }
+
+        if event.key_code != KeyCode::KeyP {
+            continue;
+        }
+        if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+            commands.queue(save_preset);
+        } else {
+            commands.queue(next_preset);
+        }
+    }
+}
+
+/// Applies the selected tile's next preset and plays it from the start
+fn next_preset(world: &mut World) {
+    let Some(tile) = selected_tile(world) else {
+        return;
+    };
+    let Some(access) = world.get::<MaterialAccess>(tile).copied() else {
+        return;
+    };
+    let Some(material) = access.get(world, tile) else {
+        return;
+    };
+    let material = material.reflect_type_path().to_string();
+    let index = world
+        .get::<PresetIndex>(tile)
+        .map_or(0, |index| index.0 + 1);
+
+    world.resource_scope(|world, presets: Mut<Presets>| {
+        let Some(presets) = presets
+            .0
+            .get(&material)
+            .filter(|presets| !presets.is_empty())
+        else {
+            info!("No presets for {material}");
+            return;
+        };
+        let index = index % presets.len();
+        let preset = &presets[index];
+
+        let mut applied = Ok(());
+        access.edit(world, tile, |material| {
+            applied = material.try_apply(preset.value.as_ref());
+        });
+        match applied {
+            Ok(()) => info!("Preset {}", preset.name),
+            Err(err) => warn!("Couldn't apply preset {}: {err}", preset.name),
+        }
+        world.entity_mut(tile).insert(PresetIndex(index));
+    });
+
+    let Some(clock) = world.get::<ClockAccess>(tile).copied() else {
+        return;
+    };
+    if let Some(mut value) = clock.get(world, tile) {
+        value.restart(world.resource::<EffectTime>().elapsed);
+        clock.set(world, tile, value);
+    }
+}
+
+/// Saves the selected tile as `preset-N.ron`, to be renamed to something better
+fn save_preset(world: &mut World) {
+    let Some(tile) = selected_tile(world) else {
+        return;
+    };
+    let Some(access) = world.get::<MaterialAccess>(tile).copied() else {
+        return;
+    };
+    let Some(material) = access.get(world, tile) else {
+        return;
+    };
+
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let serializer = ReflectSerializer::new(material.as_partial_reflect(), &registry);
+    let text = match ron::ser::to_string_pretty(&serializer, PrettyConfig::default()) {
+        Ok(text) => text,
+        Err(err) => {
+            warn!("Couldn't save preset: {err}");
+            return;
+        }
+    };
+    let type_path = material.reflect_type_path().to_string();
+    let folder = presets_path().join(preset_folder(material.reflect_short_type_path()));
+    let Ok(value) = material.reflect_clone() else {
+        return;
+    };
+    drop(registry);
+
+    let Some(name) = (1..)
+        .map(|number| format!("preset-{number}"))
+        .find(|name| !folder.join(format!("{name}.ron")).exists())
+    else {
+        return;
+    };
+    let path = folder.join(format!("{name}.ron"));
+    if let Err(err) = fs::create_dir_all(&folder).and_then(|()| fs::write(&path, text)) {
+        warn!("Couldn't save preset {}: {err}", path.display());
+        return;
+    }
+    info!("Saved preset {}", path.display());
+
+    let mut presets = world.resource_mut::<Presets>();
+    let presets = presets.0.entry(type_path).or_default();
+    presets.push(Preset {
+        name,
+        value: value.into_partial_reflect(),
+    });
+    presets.sort_by(|a, b| a.name.cmp(&b.name));
+}
@@ -0,0 +1,139 @@
+//! Per-field ranges for randomizing materials
+//!
+//! Structs declare them with `#[derive(ParamRanges)]` and `#[range(min, max)]`
+//! on their fields, and register them with `#[reflect(ParamRanges)]` so
+//! [`randomize`] can find them on nested structs too.
+
+use std::ops::RangeInclusive;
+
+use bevy::{
+    prelude::*,
+    reflect::{reflect_trait, ReflectMut, ReflectRef, TypeRegistry},
+};
+use fastrand::Rng;
+
+#[reflect_trait]
+pub trait ParamRanges {
+    /// How far a field can be randomized, or `None` to leave it alone
+    fn range(&self, field: &str) -> Option<RangeInclusive<f32>>;
+}
+
+/// Randomizes every field of `value` that has a range, looking into nested
+/// structs for more
+///
+/// A range applies to every number in its field, so a `Vec2` gets two random
+/// components and a color three random channels. Alpha is kept, it's usually
+/// what makes the effect visible at all.
+pub fn randomize(value: &mut dyn PartialReflect, registry: &TypeRegistry, rng: &mut Rng) {
+    let ranges = value.try_as_reflect().and_then(|value| {
+        registry
+            .get_type_data::<ReflectParamRanges>(value.type_id())?
+            .get(value)
+    });
+    let ranges = match value.reflect_ref() {
+        ReflectRef::Struct(fields) => (0..fields.field_len())
+            .map(|index| ranges?.range(fields.name_at(index)?))
+            .collect::<Vec<_>>(),
+        ReflectRef::TupleStruct(fields) => (0..fields.field_len())
+            .map(|index| ranges?.range(&index.to_string()))
+            .collect(),
+        _ => return,
+    };
+
+    for (index, range) in ranges.into_iter().enumerate() {
+        let field = match value.reflect_mut() {
+            ReflectMut::Struct(fields) => fields.field_at_mut(index),
+            ReflectMut::TupleStruct(fields) => fields.field_mut(index),
+            _ => None,
+        };
+        let Some(field) = field else {
+            continue;
+        };
+        match range {
+            Some(range) => randomize_numbers(field, &range, rng),
+            None => randomize(field, registry, rng),
+        }
+    }
+}
+
+fn randomize_numbers(value: &mut dyn PartialReflect, range: &RangeInclusive<f32>, rng: &mut Rng) {
+    let random = range.start() + rng.f32() * (range.end() - range.start());
+    if let Some(float) = value.try_downcast_mut::<f32>() {
+        *float = random;
+        return;
+    }
+    if let Some(int) = value.try_downcast_mut::<i32>() {
+        *int = random.round() as i32;
+        return;
+    }
+    if let Some(int) = value.try_downcast_mut::<u32>() {
+        *int = random.round().max(0.0) as u32;
+        return;
+    }
+
+    match value.reflect_mut() {
+        ReflectMut::Struct(fields) => {
+            for index in 0..fields.field_len() {
+                if fields.name_at(index) == Some("alpha") {
+                    continue;
+                }
+                if let Some(field) = fields.field_at_mut(index) {
+                    randomize_numbers(field, range, rng);
+                }
+            }
+        }
+        ReflectMut::TupleStruct(fields) => {
+            for index in 0..fields.field_len() {
+                if let Some(field) = fields.field_mut(index) {
+                    randomize_numbers(field, range, rng);
+                }
+            }
+        }
+        ReflectMut::Array(items) => {
+            for index in 0..items.len() {
+                if let Some(item) = items.get_mut(index) {
+                    randomize_numbers(item, range, rng);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::materials::LineFieldMaterial;
+
+    #[test]
+    fn stays_in_range() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<LineFieldMaterial>();
+        let mut rng = Rng::with_seed(7);
+
+        for _ in 0..100 {
+            let mut material = LineFieldMaterial::default();
+            randomize(&mut material, &registry, &mut rng);
+
+            // Nested in `pack`, found through its own registration
+            assert!((0.0..=0.2).contains(&material.pack.line_thickness));
+            assert!((1..=12).contains(&material.pack.layer_count));
+            for channel in material.base_color.to_f32_array_no_alpha() {
+                assert!((0.0..=1.0).contains(&channel));
+            }
+            assert_eq!(material.base_color.alpha, 1.0);
+            assert_eq!(material.clock, LineFieldMaterial::default().clock);
+        }
+    }
+
+    #[test]
+    fn changes_values() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<LineFieldMaterial>();
+
+        let mut material = LineFieldMaterial::default();
+        randomize(&mut material, &registry, &mut Rng::with_seed(7));
+        assert_ne!(material.pack.line_thickness, 0.01);
+        assert_ne!(material.edge_color, LinearRgba::WHITE);
+    }
+}
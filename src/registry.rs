@@ -0,0 +1,168 @@
+use std::hash::Hash;
+
+use bevy::{
+    asset::AssetPath, platform::collections::HashMap, prelude::*, reflect::GetTypeRegistration,
+    shader::ShaderRef,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    clock::{tick_effect_clocks, ClockAccess, EffectMaterial, EffectTime, TickEffectClocks},
+    debug::{apply_debug_view, DebugView},
+};
+
+/// A material that can be placed in the gallery by the manifest
+///
+/// The manifest refers to it by its type name, and its uniform values are
+/// deserialized from the tile's `params`. Missing fields fall back to `Default`.
+/// Its fields are edited live and saved as presets through `Reflect`.
+pub trait ShaderEntry:
+    EffectMaterial + Reflect + GetTypeRegistration + Default + DeserializeOwned
+{
+    const NAME: &'static str;
+    /// A line about what the effect is, shown with its name in the gallery
+    const DESCRIPTION: &'static str = "";
+    /// Words to find it by, such as `hit` or `3d`
+    const TAGS: &'static [&'static str] = &[];
+
+    /// Called after deserializing, for handles that can't be described in the manifest
+    fn load_assets(&mut self, _asset_server: &AssetServer) {}
+}
+
+pub trait RegisterShaderTile {
+    fn register_shader_tile<M: ShaderEntry>(&mut self) -> &mut Self
+    where
+        M::Data: PartialEq + Eq + Hash + Clone;
+}
+
+impl RegisterShaderTile for App {
+    fn register_shader_tile<M: ShaderEntry>(&mut self) -> &mut Self
+    where
+        M::Data: PartialEq + Eq + Hash + Clone,
+    {
+        self.add_plugins(MaterialPlugin::<M>::default())
+            .register_type::<M>()
+            .add_systems(
+                Update,
+                (
+                    tick_effect_clocks::<M>.in_set(TickEffectClocks),
+                    apply_debug_view::<M>,
+                ),
+            );
+        self.init_resource::<ShaderRegistry>()
+            .init_resource::<EffectTime>()
+            .init_resource::<DebugView>();
+        self.world_mut()
+            .resource_mut::<ShaderRegistry>()
+            .entries
+            .insert(
+                M::short_type_path(),
+                RegistryEntry {
+                    name: M::NAME,
+                    description: M::DESCRIPTION,
+                    tags: M::TAGS,
+                    fragment_shader: match M::fragment_shader() {
+                        ShaderRef::Path(path) => Some(path),
+                        _ => None,
+                    },
+                    insert_material: insert_material::<M>,
+                },
+            );
+        self
+    }
+}
+
+pub type InsertMaterial = fn(&mut EntityWorldMut, Option<ron::Value>) -> ron::Result<()>;
+
+#[derive(Debug, Resource, Default)]
+pub struct ShaderRegistry {
+    entries: HashMap<&'static str, RegistryEntry>,
+}
+
+impl ShaderRegistry {
+    /// Looks up a material by its type name, as written in the manifest
+    pub fn get(&self, material: &str) -> Option<RegistryEntry> {
+        self.entries.get(material).cloned()
+    }
+
+    /// Paths of the fragment shaders used by registered materials
+    pub fn fragment_shaders(&self) -> impl Iterator<Item = &AssetPath<'static>> {
+        self.entries
+            .values()
+            .filter_map(|entry| entry.fragment_shader.as_ref())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RegistryEntry {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub tags: &'static [&'static str],
+    pub fragment_shader: Option<AssetPath<'static>>,
+    pub insert_material: InsertMaterial,
+}
+
+fn insert_material<M: ShaderEntry>(
+    tile: &mut EntityWorldMut,
+    params: Option<ron::Value>,
+) -> ron::Result<()> {
+    let mut material = match params {
+        Some(params) => params.into_rust::<M>()?,
+        None => M::default(),
+    };
+
+    let handle = tile.world_scope(|world| {
+        material
+            .clock_mut()
+            .restart(world.resource::<EffectTime>().elapsed);
+        material.load_assets(world.resource::<AssetServer>());
+        world.resource_mut::<Assets<M>>().add(material)
+    });
+    tile.insert((
+        MeshMaterial3d(handle),
+        ClockAccess::of::<M>(),
+        MaterialAccess::of::<M>(),
+    ));
+    Ok(())
+}
+
+pub type EditMaterial = fn(&mut World, Entity, &mut dyn FnMut(&mut dyn Reflect));
+
+/// Reads and edits the material of an entity through `Reflect`, whatever its type
+///
+/// Lets the gallery's inspector list and change the fields of any material
+/// without knowing about it.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct MaterialAccess {
+    get: for<'w> fn(&'w World, Entity) -> Option<&'w dyn Reflect>,
+    edit: EditMaterial,
+}
+
+impl MaterialAccess {
+    pub fn of<M: Material + Reflect>() -> Self {
+        Self {
+            get: |world, entity| {
+                let handle = world.get::<MeshMaterial3d<M>>(entity)?;
+                let material = world.resource::<Assets<M>>().get(handle)?;
+                Some(material.as_reflect())
+            },
+            edit: |world, entity, edit| {
+                let Some(handle) = world.get::<MeshMaterial3d<M>>(entity).cloned() else {
+                    return;
+                };
+                if let Some(material) = world.resource_mut::<Assets<M>>().get_mut(&handle) {
+                    edit(material.as_reflect_mut());
+                }
+            },
+        }
+    }
+
+    pub fn get<'w>(&self, world: &'w World, entity: Entity) -> Option<&'w dyn Reflect> {
+        (self.get)(world, entity)
+    }
+
+    /// Runs `edit` on the material, which then gets uploaded again
+    pub fn edit(&self, world: &mut World, entity: Entity, mut edit: impl FnMut(&mut dyn Reflect)) {
+        (self.edit)(world, entity, &mut edit);
+    }
+}
@@ -0,0 +1,220 @@
+use bevy::{
+    ecs::system::SystemParam,
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+
+use crate::{labels::TileInfo, Selected, Tile};
+
+/// Narrows the grid down to the tiles whose name or tags match what's typed
+///
+/// Slash starts typing, Enter keeps the filter and Escape clears it. The other
+/// shortcuts are ignored while typing, as long as they read keys through
+/// [`Hotkeys`].
+pub struct SearchPlugin;
+
+impl Plugin for SearchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Search>()
+            .add_systems(Startup, spawn_search_box)
+            .add_systems(
+                Update,
+                (search_keys, filter_tiles, update_search_box).chain(),
+            );
+    }
+}
+
+#[derive(Debug, Default, Resource)]
+pub struct Search {
+    query: String,
+    typing: bool,
+}
+
+impl Search {
+    /// Whether every word of the query is part of the tile's name or a tag
+    fn matches(&self, info: &TileInfo) -> bool {
+        let name = info.name.to_lowercase();
+        self.query.to_lowercase().split_whitespace().all(|word| {
+            let word = word.trim_start_matches('#');
+            name.contains(word)
+                || info
+                    .tags
+                    .iter()
+                    .any(|tag| tag.to_lowercase().contains(word))
+        })
+    }
+}
+
+/// Hidden from the grid by the search, and skipped when selecting tiles
+#[derive(Debug, Component)]
+pub struct FilteredOut;
+
+/// Key presses meant for the gallery's shortcuts rather than the search box
+#[derive(SystemParam)]
+pub struct Hotkeys<'w, 's> {
+    events: MessageReader<'w, 's, KeyboardInput>,
+    search: Res<'w, Search>,
+}
+
+impl Hotkeys<'_, '_> {
+    /// Like `MessageReader::read`, but empty while typing a search
+    pub fn read(&mut self) -> impl Iterator<Item = &KeyboardInput> {
+        let typing = self.search.typing;
+        self.events.read().filter(move |_| !typing)
+    }
+}
+
+#[derive(Debug, Component)]
+struct SearchBox;
+
+fn spawn_search_box(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Px(10.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![(
+            SearchBox,
+            Text::default(),
+            Node {
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::BLACK.with_alpha(0.6)),
+        )],
+    ));
+}
+
+fn search_keys(
+    mut keyboard_input_events: MessageReader<KeyboardInput>,
+    mut search: ResMut<Search>,
+) {
+    for event in keyboard_input_events.read() {
+        if event.state == ButtonState::Released {
+            continue;
+        }
+        if !search.typing {
+            if event.key_code == KeyCode::Slash && !event.repeat {
+                search.typing = true;
+            }
+            continue;
+        }
+
+        match (event.key_code, &event.logical_key) {
+            (KeyCode::Enter, _) => search.typing = false,
+            (KeyCode::Escape, _) => {
+                search.typing = false;
+                search.query.clear();
+            }
+            (KeyCode::Backspace, _) => {
+                search.query.pop();
+            }
+            (_, Key::Space) => search.query.push(' '),
+            (_, Key::Character(text)) => search.query.push_str(text),
+            _ => {}
+        }
+    }
+}
+
+/// Hides the tiles that don't match, including ones spawned after searching
+fn filter_tiles(
+    mut commands: Commands,
+    search: Res<Search>,
+    mut last_query: Local<String>,
+    mut selected: ResMut<Selected>,
+    tiles: Query<(Entity, &TileInfo, Has<FilteredOut>)>,
+    added: Query<(), Added<TileInfo>>,
+) {
+    let query_changed = *last_query != search.query;
+    if !query_changed && added.is_empty() {
+        return;
+    }
+    if query_changed {
+        last_query.clone_from(&search.query);
+        selected.0 = 0;
+    }
+
+    for (tile, info, filtered_out) in &tiles {
+        match (search.matches(info), filtered_out) {
+            (true, true) => {
+                commands
+                    .entity(tile)
+                    .remove::<FilteredOut>()
+                    .insert(Visibility::Inherited);
+            }
+            (false, false) => {
+                commands
+                    .entity(tile)
+                    .insert((FilteredOut, Visibility::Hidden));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_search_box(
+    search: Res<Search>,
+    tiles: Query<Has<FilteredOut>, With<Tile>>,
+    search_box: Single<(&mut Text, &mut Node), With<SearchBox>>,
+) {
+    let (mut text, mut node) = search_box.into_inner();
+    let display = if search.typing || !search.query.is_empty() {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    if node.display != display {
+        node.display = display;
+    }
+    if display == Display::None {
+        return;
+    }
+
+    let shown = tiles.iter().filter(|filtered_out| !filtered_out).count();
+    let cursor = if search.typing { "_" } else { "" };
+    let value = format!(
+        "Search: {}{cursor}   {shown} of {}",
+        search.query,
+        tiles.iter().count()
+    );
+    if text.0 != value {
+        text.0 = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(query: &str) -> Search {
+        Search {
+            query: query.to_string(),
+            typing: false,
+        }
+    }
+
+    #[test]
+    fn matches_names_and_tags_in_any_case() {
+        let info = TileInfo {
+            name: "Lightning Strike".to_string(),
+            description: String::new(),
+            tags: vec!["Electric".to_string(), "loop".to_string()],
+            shader: None,
+        };
+
+        assert!(search("light").matches(&info));
+        assert!(search("#electric").matches(&info));
+        assert!(search("#ELECTRIC").matches(&info));
+        assert!(search("strike #loop").matches(&info));
+        assert!(search("").matches(&info));
+        assert!(!search("strike #fire").matches(&info));
+        assert!(!search("#thunder").matches(&info));
+    }
+}
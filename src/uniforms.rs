@@ -0,0 +1,29 @@
+use bevy::render::render_resource::{
+    encase::{internal::WriteInto, UniformBuffer},
+    ShaderType,
+};
+
+/// A `#[uniform(N)]` field of a material, encoded the way `AsBindGroup` uploads it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniformBinding {
+    pub binding: u32,
+    /// Name of the field, which is also its `Reflect` path
+    pub field: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+/// The uniforms of a material, for checking them against its shader
+///
+/// `#[derive(ShaderMaterial)]` implements this for every `#[uniform(N)]` field.
+pub trait MaterialUniforms {
+    fn uniforms(&self) -> Vec<UniformBinding>;
+}
+
+/// Encodes a value with the layout WGSL gives it in a uniform buffer
+pub fn uniform_bytes<T: ShaderType + WriteInto>(value: &T) -> Vec<u8> {
+    let mut buffer = UniformBuffer::new(Vec::new());
+    buffer
+        .write(value)
+        .expect("writing to a Vec can't run out of space");
+    buffer.into_inner()
+}
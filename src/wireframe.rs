@@ -0,0 +1,204 @@
+use bevy::{
+    camera::visibility::RenderLayers,
+    input::ButtonState,
+    mesh::{PrimitiveTopology, VertexAttributeValues},
+    platform::collections::HashSet,
+    prelude::*,
+    transform::TransformSystems,
+};
+use bevy_shader_testing::materials::RippleMaterial;
+
+use crate::{
+    camera::{FocusCamera, FOCUS_LAYER},
+    grid::Previewed,
+    search::Hotkeys,
+};
+
+/// In world units, at preview size
+const NORMAL_LENGTH: f32 = 0.1;
+const EDGE_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const NORMAL_COLOR: Color = Color::srgb(0.2, 0.6, 1.0);
+
+/// Draws the edges and normals of the focused tile's mesh over it, to see what
+/// a vertex stage does to the geometry
+///
+/// F4 steps through the wireframe, the wireframe with normals, and neither. The
+/// ripple is displaced on the CPU the same way `ripple.wgsl` does it, so its
+/// lines follow the wave. Other effects show their mesh as it goes into the
+/// vertex stage.
+pub struct WireframeOverlayPlugin;
+
+impl Plugin for WireframeOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Overlay>()
+            .insert_gizmo_config(
+                OverlayGizmos,
+                GizmoConfig {
+                    // Over the effect, back faces included
+                    depth_bias: -1.0,
+                    render_layers: RenderLayers::layer(FOCUS_LAYER),
+                    ..default()
+                },
+            )
+            .add_systems(Update, overlay_keys)
+            .add_systems(PostUpdate, draw_overlay.after(TransformSystems::Propagate));
+    }
+}
+
+/// Drawn by the focus camera, with the previews
+#[derive(Default, Reflect, GizmoConfigGroup)]
+struct OverlayGizmos;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Resource)]
+enum Overlay {
+    #[default]
+    Off,
+    Wireframe,
+    WireframeAndNormals,
+}
+
+fn overlay_keys(mut hotkeys: Hotkeys, mut overlay: ResMut<Overlay>) {
+    for event in hotkeys.read() {
+        if event.repeat || event.state == ButtonState::Released || event.key_code != KeyCode::F4 {
+            continue;
+        }
+        *overlay = match *overlay {
+            Overlay::Off => Overlay::Wireframe,
+            Overlay::Wireframe => Overlay::WireframeAndNormals,
+            Overlay::WireframeAndNormals => Overlay::Off,
+        };
+        info!("Overlay: {overlay:?}");
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn draw_overlay(
+    overlay: Res<Overlay>,
+    mut gizmos: Gizmos<OverlayGizmos>,
+    tiles: Query<
+        (
+            &Mesh3d,
+            &GlobalTransform,
+            &Visibility,
+            Option<&MeshMaterial3d<RippleMaterial>>,
+        ),
+        With<Previewed>,
+    >,
+    camera: Single<(&Camera, &GlobalTransform), With<FocusCamera>>,
+    meshes: Res<Assets<Mesh>>,
+    ripples: Res<Assets<RippleMaterial>>,
+) {
+    if *overlay == Overlay::Off {
+        return;
+    }
+    let (camera, camera_transform) = *camera;
+    let clip_from_world = camera.clip_from_view() * camera_transform.to_matrix().inverse();
+    let world_from_clip = clip_from_world.inverse();
+
+    for (mesh, transform, visibility, ripple) in &tiles {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+        let Some(geometry) = meshes.get(mesh).and_then(Geometry::of) else {
+            continue;
+        };
+        let ripple = ripple.and_then(|ripple| ripples.get(ripple));
+
+        let positions = geometry
+            .positions
+            .iter()
+            .zip(&geometry.normals)
+            .zip(&geometry.uvs)
+            .map(|((&position, &normal), &uv)| {
+                let world = transform.transform_point(position);
+                let Some(ripple) = ripple else {
+                    return world;
+                };
+                // Pushed along the mesh normal after projecting, as in the shader
+                let mut clip = clip_from_world * world.extend(1.0);
+                clip += (normal * ripple.height(uv)).extend(0.0);
+                world_from_clip.project_point3(clip.truncate() / clip.w)
+            })
+            .collect::<Vec<_>>();
+
+        for (a, b) in geometry.edges() {
+            gizmos.line(positions[a], positions[b], EDGE_COLOR);
+        }
+
+        if *overlay == Overlay::WireframeAndNormals {
+            for (position, normal) in positions.iter().zip(&geometry.normals) {
+                let direction = (transform.rotation() * *normal).normalize_or_zero();
+                gizmos.line(
+                    *position,
+                    position + direction * NORMAL_LENGTH,
+                    NORMAL_COLOR,
+                );
+            }
+        }
+    }
+}
+
+/// The parts of a triangle mesh the overlay needs, in mesh space
+struct Geometry {
+    positions: Vec<Vec3>,
+    normals: Vec<Vec3>,
+    uvs: Vec<Vec2>,
+    triangles: Vec<usize>,
+}
+
+impl Geometry {
+    fn of(mesh: &Mesh) -> Option<Self> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)?
+            .as_float3()?
+            .iter()
+            .copied()
+            .map(Vec3::from)
+            .collect::<Vec<_>>();
+        let normals = match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(normals) => normals
+                .as_float3()?
+                .iter()
+                .copied()
+                .map(Vec3::from)
+                .collect(),
+            None => vec![Vec3::ZERO; positions.len()],
+        };
+        let uvs = match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+            Some(VertexAttributeValues::Float32x2(uvs)) => {
+                uvs.iter().copied().map(Vec2::from).collect()
+            }
+            _ => vec![Vec2::ZERO; positions.len()],
+        };
+        if normals.len() != positions.len() || uvs.len() != positions.len() {
+            return None;
+        }
+        let triangles = match mesh.indices() {
+            Some(indices) => indices.iter().collect(),
+            None => (0..positions.len()).collect(),
+        };
+        Some(Self {
+            positions,
+            normals,
+            uvs,
+            triangles,
+        })
+    }
+
+    /// Each edge once, even when triangles share it
+    fn edges(&self) -> HashSet<(usize, usize)> {
+        self.triangles
+            .chunks_exact(3)
+            .flat_map(|triangle| {
+                [(0, 1), (1, 2), (2, 0)].map(|(a, b)| {
+                    let (a, b) = (triangle[a], triangle[b]);
+                    (a.min(b), a.max(b))
+                })
+            })
+            .filter(|&(_, b)| b < self.positions.len())
+            .collect()
+    }
+}
@@ -0,0 +1,287 @@
+//! Checks every material's uniforms against the bindings its shader declares
+//!
+//! The shaders are laid out by naga and the Rust side is encoded the way
+//! `AsBindGroup` uploads it, so this runs without a GPU. Fields are matched by
+//! binding, then every scalar the shader reads has to line up with a Rust
+//! scalar of the same type. Rust values the shader doesn't read, like the
+//! padding that rounds uniforms up to 16 bytes for WebGL, are allowed.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use bevy::{
+    prelude::*,
+    reflect::{GetPath, ReflectRef},
+    shader::ShaderRef,
+};
+use bevy_shader_testing::{materials::*, uniforms::MaterialUniforms};
+use naga::{AddressSpace, ArraySize, Handle, Module, Scalar, TypeInner};
+
+const SHADER_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/shaders");
+
+/// Paths to the scalars of a uniform, and their types, by byte offset
+type Scalars = BTreeMap<u32, (String, Scalar)>;
+
+macro_rules! layout_tests {
+    ($($test:ident: $material:ty,)*) => {
+        $(
+            #[test]
+            fn $test() {
+                check::<$material>();
+            }
+        )*
+    };
+}
+
+layout_tests! {
+    fresnel: FresnelMaterial,
+    line_field: LineFieldMaterial,
+    multi_ripple_ring: MultiRippleRingMaterial,
+    ripple_ring: RippleRingMaterial,
+    hit_spark: HitSparkMaterial,
+    block: BlockMaterial,
+    clink: ClinkMaterial,
+    spinner: SpinnerMaterial,
+    focal_line: FocalLineMaterial,
+    edge_slash: EdgeSlashMaterial,
+    burst: BurstMaterial,
+    corner_slash: CornerSlashMaterial,
+    lightning: LightningMaterial,
+    rocks: RocksMaterial,
+    jackpot: Jackpot,
+    ripple: RippleMaterial,
+    fire: FireMaterial,
+    smoke_bomb: SmokeBombMaterial,
+    sparks: SparksMaterial,
+    vertex_test: VertexTest,
+    bezier: BezierMaterial,
+    bezier_swoosh: BezierSwooshMaterial,
+    normal_cube: NormalCubeMaterial,
+    sugar_coat: SugarCoatMaterial,
+    bill_burst: BillBurstMaterial,
+    particles: ParticlesMaterial,
+    droplet: DropletMaterial,
+    mandelbrot: MandelbrotMaterial,
+}
+
+fn check<M: Material + MaterialUniforms + Reflect + Clone + Default>() {
+    let ShaderRef::Path(path) = M::fragment_shader() else {
+        panic!("{} has no shader to check", M::short_type_path());
+    };
+    let file = path.path().file_name().unwrap().to_string_lossy();
+    let module = parse(&file);
+
+    let mut declared = module
+        .global_variables
+        .iter()
+        .filter(|(_, var)| var.space == AddressSpace::Uniform)
+        .filter_map(|(_, var)| Some((var.binding.as_ref()?.binding, var)))
+        .collect::<BTreeMap<_, _>>();
+
+    let material = M::default();
+    let mut errors = Vec::new();
+    for uniform in material.uniforms() {
+        let field = uniform.field;
+        let Some(var) = declared.remove(&uniform.binding) else {
+            errors.push(format!(
+                "`{field}` is bound to {} but the shader doesn't declare it",
+                uniform.binding
+            ));
+            continue;
+        };
+        let name = var.name.as_deref().unwrap_or("?");
+
+        let size = module.types[var.ty].inner.size(module.to_ctx());
+        if uniform.bytes.len() as u32 != size {
+            errors.push(format!(
+                "`{field}` is {} bytes but `{name}` is {size}",
+                uniform.bytes.len()
+            ));
+        }
+
+        let mut wgsl = Scalars::new();
+        wgsl_scalars(&module, var.ty, name.to_string(), 0, &mut wgsl);
+        let rust = rust_scalars(&material, field, uniform.binding);
+        for (offset, (path, scalar)) in wgsl {
+            let scalar = scalar_name(scalar);
+            match rust.get(&offset) {
+                Some((_, found)) if scalar_name(*found) == scalar => {}
+                Some((found_path, found)) => errors.push(format!(
+                    "`{path}` is {scalar} at byte {offset}, but `{found_path}` is {}",
+                    scalar_name(*found)
+                )),
+                None => errors.push(format!(
+                    "`{path}` is {scalar} at byte {offset}, but `{field}` only pads it"
+                )),
+            }
+        }
+    }
+    for (binding, var) in declared {
+        errors.push(format!(
+            "`{}` is declared at binding {binding} but no field is bound to it",
+            var.name.as_deref().unwrap_or("?")
+        ));
+    }
+
+    assert!(
+        errors.is_empty(),
+        "{} doesn't match {file}:\n{}",
+        M::short_type_path(),
+        errors.join("\n")
+    );
+}
+
+/// Parses the structs and material uniforms of a shader
+///
+/// Functions are left out, so imports from Bevy don't need to be resolved.
+fn parse(file: &str) -> Module {
+    let shader = Path::new(SHADER_DIR).join(file);
+    let source = fs::read_to_string(&shader).unwrap();
+    let effect_time = fs::read_to_string(Path::new(SHADER_DIR).join("effect_time.wgsl")).unwrap();
+    let declarations = declarations(&effect_time) + &declarations(&source);
+    naga::front::wgsl::parse_str(&declarations)
+        .unwrap_or_else(|err| panic!("{}", err.emit_to_string(&declarations)))
+}
+
+fn declarations(source: &str) -> String {
+    let mut declarations = String::new();
+    let mut in_struct = false;
+    for line in source.lines() {
+        let line = line.trim();
+        in_struct |= line.starts_with("struct ");
+        if in_struct || line.contains("#{MATERIAL_BIND_GROUP}") && line.contains("var<uniform>") {
+            declarations += &line.replace("#{MATERIAL_BIND_GROUP}", "2");
+            declarations += "\n";
+        }
+        in_struct &= !line.starts_with('}');
+    }
+    declarations
+}
+
+fn wgsl_scalars(
+    module: &Module,
+    ty: Handle<naga::Type>,
+    path: String,
+    offset: u32,
+    scalars: &mut Scalars,
+) {
+    match module.types[ty].inner {
+        TypeInner::Scalar(scalar) => {
+            scalars.insert(offset, (path, scalar));
+        }
+        TypeInner::Vector { size, scalar } => {
+            for (index, component) in ["x", "y", "z", "w"].iter().take(size as usize).enumerate() {
+                let offset = offset + index as u32 * scalar.width as u32;
+                scalars.insert(offset, (format!("{path}.{component}"), scalar));
+            }
+        }
+        TypeInner::Array {
+            base,
+            size: ArraySize::Constant(count),
+            stride,
+        } => {
+            for index in 0..count.get() {
+                let path = format!("{path}[{index}]");
+                wgsl_scalars(module, base, path, offset + index * stride, scalars);
+            }
+        }
+        TypeInner::Struct { ref members, .. } => {
+            for member in members {
+                let name = member.name.as_deref().unwrap_or("?");
+                let path = format!("{path}.{name}");
+                wgsl_scalars(module, member.ty, path, offset + member.offset, scalars);
+            }
+        }
+        ref other => panic!("no layout check for {other:?} uniforms yet"),
+    }
+}
+
+/// Finds where each scalar of `field` ends up by changing it and seeing which
+/// bytes change
+fn rust_scalars<M: MaterialUniforms + Reflect + Clone>(
+    material: &M,
+    field: &str,
+    binding: u32,
+) -> Scalars {
+    let bytes = |material: &M| {
+        material
+            .uniforms()
+            .into_iter()
+            .find(|uniform| uniform.binding == binding)
+            .unwrap()
+            .bytes
+    };
+    let original = bytes(material);
+
+    let mut paths = Vec::new();
+    scalar_paths(
+        material.reflect_path(field).unwrap(),
+        field.to_string(),
+        &mut paths,
+    );
+
+    let mut scalars = Scalars::new();
+    for (path, scalar) in paths {
+        let mut changed = material.clone();
+        nudge(changed.reflect_path_mut(path.as_str()).unwrap());
+        let first_change = original
+            .iter()
+            .zip(bytes(&changed))
+            .position(|(before, after)| *before != after)
+            .unwrap_or_else(|| panic!("`{path}` isn't uploaded"));
+        // Scalars are 4 byte aligned, the first changed byte can be any of them
+        scalars.insert(first_change as u32 / 4 * 4, (path, scalar));
+    }
+    scalars
+}
+
+fn scalar_paths(value: &dyn PartialReflect, path: String, paths: &mut Vec<(String, Scalar)>) {
+    if value.try_downcast_ref::<f32>().is_some() {
+        return paths.push((path, Scalar::F32));
+    }
+    if value.try_downcast_ref::<i32>().is_some() {
+        return paths.push((path, Scalar::I32));
+    }
+    if value.try_downcast_ref::<u32>().is_some() {
+        return paths.push((path, Scalar::U32));
+    }
+
+    match value.reflect_ref() {
+        ReflectRef::Struct(fields) => {
+            for (index, field) in fields.iter_fields().enumerate() {
+                let name = fields.name_at(index).unwrap();
+                scalar_paths(field, format!("{path}.{name}"), paths);
+            }
+        }
+        ReflectRef::TupleStruct(fields) => {
+            for (index, field) in fields.iter_fields().enumerate() {
+                scalar_paths(field, format!("{path}.{index}"), paths);
+            }
+        }
+        ReflectRef::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                scalar_paths(item, format!("{path}[{index}]"), paths);
+            }
+        }
+        _ => panic!("no layout check for `{path}` yet"),
+    }
+}
+
+/// Changes a scalar to some other value
+fn nudge(scalar: &mut dyn PartialReflect) {
+    if let Some(float) = scalar.try_downcast_mut::<f32>() {
+        *float = if *float == 1.0 { 2.0 } else { 1.0 };
+    } else if let Some(int) = scalar.try_downcast_mut::<i32>() {
+        *int = if *int == 1 { 2 } else { 1 };
+    } else if let Some(int) = scalar.try_downcast_mut::<u32>() {
+        *int = if *int == 1 { 2 } else { 1 };
+    }
+}
+
+fn scalar_name(scalar: Scalar) -> String {
+    match scalar {
+        Scalar::F32 => "f32".to_string(),
+        Scalar::I32 => "i32".to_string(),
+        Scalar::U32 => "u32".to_string(),
+        other => format!("{other:?}"),
+    }
+}